@@ -0,0 +1,547 @@
+//! Keyframe animation of node styles: the numeric fields are interpolated between keyframes, and the discrete ones snap.
+//! Played back on a copy of the tree that is laid out again whenever the time changes, to see how the layout moves.
+use std::collections::HashMap;
+
+use egui::{Color32, Stroke};
+use taffy::{
+    AvailableSpace, Dimension, LengthPercentage, LengthPercentageAuto, NodeId, Rect, Size, Style,
+    TaffyTree, TraversePartialTree,
+};
+
+use crate::style_field::set_style_if_changed;
+
+/// The style of a node at a point of the timeline.
+#[derive(Debug, Clone)]
+pub struct Keyframe {
+    /// In seconds.
+    pub time: f32,
+    pub style: Style,
+}
+
+#[derive(Debug)]
+pub struct Timeline {
+    /// Shows the animated styles in Node Visuals, instead of the styles of the tree.
+    pub enabled: bool,
+    pub playing: bool,
+    pub looping: bool,
+    /// The current time, in seconds.
+    pub time: f32,
+    pub duration: f32,
+    /// The keyframes of each animated node, sorted by time.
+    pub keyframes: HashMap<NodeId, Vec<Keyframe>>,
+    /// Incremented on every change of the keyframes, to know when the [`AnimatedTree`] is stale.
+    pub version: usize,
+    /// A node that moves or resizes by more than this many pixels in one step, and much more than it usually does, jumps.
+    pub jump_threshold: f32,
+}
+impl Default for Timeline {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            playing: false,
+            looping: true,
+            time: 0.0,
+            duration: 2.0,
+            keyframes: HashMap::new(),
+            version: 0,
+            jump_threshold: 10.0,
+        }
+    }
+}
+
+/// A copy of the tree with the styles at a time of the timeline, laid out.
+#[derive(Debug)]
+pub struct AnimatedTree {
+    /// The edit count of the tree, the version of the keyframes, the time and the available space of the layout.
+    key: (usize, usize, f32, Size<AvailableSpace>),
+    pub tree: TaffyTree,
+    /// How many times it was laid out, to know when the layout changed.
+    pub layouts: usize,
+}
+
+/// The number of steps of the timeline that are laid out when looking for jumps.
+const ANALYSIS_STEPS: usize = 200;
+
+impl Timeline {
+    /// Advances the playback by `dt` seconds.
+    pub fn advance(&mut self, dt: f32) {
+        if !self.playing {
+            return;
+        }
+        self.time += dt;
+        if self.time >= self.duration {
+            if self.looping {
+                self.time %= self.duration.max(f32::EPSILON);
+            } else {
+                self.time = self.duration;
+                self.playing = false;
+            }
+        }
+    }
+    /// Adds a keyframe, or replaces the keyframe of the node at the same time.
+    pub fn set_keyframe(&mut self, node: NodeId, time: f32, style: Style) {
+        let keyframes = self.keyframes.entry(node).or_default();
+        match keyframes
+            .iter_mut()
+            .find(|keyframe| (keyframe.time - time).abs() < 1e-3)
+        {
+            Some(keyframe) => keyframe.style = style,
+            None => {
+                keyframes.push(Keyframe { time, style });
+                keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+            }
+        }
+        self.version += 1;
+    }
+    pub fn remove_keyframe(&mut self, node: NodeId, index: usize) {
+        let Some(keyframes) = self.keyframes.get_mut(&node) else {
+            return;
+        };
+        keyframes.remove(index);
+        if keyframes.is_empty() {
+            self.keyframes.remove(&node);
+        }
+        self.version += 1;
+    }
+    /// Forgets the keyframes of a removed node.
+    pub fn detach(&mut self, node: NodeId) {
+        if self.keyframes.remove(&node).is_some() {
+            self.version += 1;
+        }
+    }
+    /// Sets the styles of the animated nodes to their interpolated styles at `time`.
+    fn set_styles(&self, tree: &mut TaffyTree, time: f32) {
+        for (&node, keyframes) in &self.keyframes {
+            if let Some(style) = style_at(keyframes, time) {
+                set_style_if_changed(tree, node, style);
+            }
+        }
+    }
+    /// Updates `animated` to the styles at the current time. The tree is copied again after it was edited
+    /// (`tree_edits` is its edit count), the keyframes changed or the available space changed, as the responsive width
+    /// switches the style variants of the tree without counting as an edit. Otherwise it's only laid out again when the time changed.
+    pub fn update_animated_tree(
+        &self,
+        animated: &mut Option<AnimatedTree>,
+        tree: &TaffyTree,
+        root: NodeId,
+        available_space: Size<AvailableSpace>,
+        tree_edits: usize,
+    ) {
+        let key = (tree_edits, self.version, self.time, available_space);
+        match animated {
+            Some(animated) if animated.key == key => {}
+            Some(animated)
+                if (animated.key.0, animated.key.1, animated.key.3)
+                    == (tree_edits, self.version, available_space) =>
+            {
+                self.set_styles(&mut animated.tree, self.time);
+                animated.tree.compute_layout(root, available_space).unwrap();
+                animated.key = key;
+                animated.layouts += 1;
+            }
+            _ => {
+                let mut copy = tree.clone();
+                self.set_styles(&mut copy, self.time);
+                copy.compute_layout(root, available_space).unwrap();
+                *animated = Some(AnimatedTree {
+                    key,
+                    tree: copy,
+                    layouts: animated.as_ref().map_or(0, |animated| animated.layouts) + 1,
+                });
+            }
+        }
+    }
+    /// Lays out a copy of the tree at every step of the timeline, and finds how far the nodes move in each step.
+    pub fn analyze(
+        &self,
+        tree: &TaffyTree,
+        root: NodeId,
+        available_space: Size<AvailableSpace>,
+    ) -> MotionAnalysis {
+        let mut tree = tree.clone();
+        let mut nodes = vec![root];
+        let mut i = 0;
+        while i < nodes.len() {
+            nodes.extend(tree.child_ids(nodes[i]));
+            i += 1;
+        }
+        let step = self.duration / ANALYSIS_STEPS as f32;
+        let mut previous: Option<Vec<[f32; 4]>> = None;
+        // how far each node moved in each step
+        let mut node_movements: Vec<Vec<f32>> = vec![vec![]; nodes.len()];
+        for i in 0..=ANALYSIS_STEPS {
+            self.set_styles(&mut tree, step * i as f32);
+            tree.compute_layout(root, available_space).unwrap();
+            let rects: Vec<[f32; 4]> = nodes
+                .iter()
+                .map(|&node| {
+                    let layout = tree.layout(node).unwrap();
+                    [
+                        layout.location.x,
+                        layout.location.y,
+                        layout.size.width,
+                        layout.size.height,
+                    ]
+                })
+                .collect();
+            if let Some(previous) = &previous {
+                for (movements, (a, b)) in
+                    node_movements.iter_mut().zip(previous.iter().zip(&rects))
+                {
+                    movements.push(a.iter().zip(b).map(|(a, b)| (a - b).abs()).sum());
+                }
+            }
+            previous = Some(rects);
+        }
+        let movements: Vec<f32> = (0..ANALYSIS_STEPS)
+            .map(|step| {
+                node_movements
+                    .iter()
+                    .map(|movements| movements[step])
+                    .fold(0.0, f32::max)
+            })
+            .collect();
+        // a node jumps when it moves much further than it usually does in a step
+        let medians: Vec<f32> = node_movements
+            .iter()
+            .map(|movements| {
+                let mut sorted = movements.clone();
+                sorted.sort_by(f32::total_cmp);
+                sorted[sorted.len() / 2]
+            })
+            .collect();
+        let jumps = (0..ANALYSIS_STEPS)
+            .filter_map(|index| {
+                let jumping: Vec<(NodeId, f32)> = nodes
+                    .iter()
+                    .zip(&node_movements)
+                    .zip(&medians)
+                    .filter_map(|((&node, movements), &median)| {
+                        let distance = movements[index];
+                        (distance > self.jump_threshold && distance > 4.0 * median)
+                            .then_some((node, distance))
+                    })
+                    .collect();
+                let &(node, distance) = jumping.iter().max_by(|a, b| a.1.total_cmp(&b.1))?;
+                Some(Jump {
+                    time: step * (index + 1) as f32,
+                    node,
+                    distance,
+                    other_nodes: jumping.len() - 1,
+                })
+            })
+            .collect();
+        MotionAnalysis {
+            step,
+            movements,
+            jumps,
+        }
+    }
+}
+
+/// The style at `time`, between the keyframes around it.
+fn style_at(keyframes: &[Keyframe], time: f32) -> Option<Style> {
+    let next = keyframes.iter().position(|keyframe| keyframe.time > time);
+    match next {
+        Some(0) => keyframes.first().map(|keyframe| keyframe.style.clone()),
+        Some(next) => {
+            let (a, b) = (&keyframes[next - 1], &keyframes[next]);
+            let t = (time - a.time) / (b.time - a.time);
+            Some(interpolate(&a.style, &b.style, t))
+        }
+        None => keyframes.last().map(|keyframe| keyframe.style.clone()),
+    }
+}
+
+/// Interpolates the numeric fields. The other fields (and values of different units, like a length and a percentage)
+/// switch from `a` to `b` halfway, like discrete css animations.
+fn interpolate(a: &Style, b: &Style, t: f32) -> Style {
+    let mut style = if t < 0.5 { a.clone() } else { b.clone() };
+    style.scrollbar_width = a.scrollbar_width.lerp(&b.scrollbar_width, t);
+    style.inset = a.inset.lerp(&b.inset, t);
+    style.size = a.size.lerp(&b.size, t);
+    style.min_size = a.min_size.lerp(&b.min_size, t);
+    style.max_size = a.max_size.lerp(&b.max_size, t);
+    style.aspect_ratio = a.aspect_ratio.lerp(&b.aspect_ratio, t);
+    style.margin = a.margin.lerp(&b.margin, t);
+    style.padding = a.padding.lerp(&b.padding, t);
+    style.border = a.border.lerp(&b.border, t);
+    style.gap = a.gap.lerp(&b.gap, t);
+    style.flex_basis = a.flex_basis.lerp(&b.flex_basis, t);
+    style.flex_grow = a.flex_grow.lerp(&b.flex_grow, t);
+    style.flex_shrink = a.flex_shrink.lerp(&b.flex_shrink, t);
+    style
+}
+
+trait Lerp: Clone {
+    fn lerp(&self, other: &Self, t: f32) -> Self;
+}
+/// Values that can't be interpolated switch halfway.
+fn snap<T: Clone>(a: &T, b: &T, t: f32) -> T {
+    if t < 0.5 {
+        a.clone()
+    } else {
+        b.clone()
+    }
+}
+impl Lerp for f32 {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self + (other - self) * t
+    }
+}
+impl Lerp for Option<f32> {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Some(a), Some(b)) => Some(a.lerp(b, t)),
+            _ => snap(self, other, t),
+        }
+    }
+}
+impl Lerp for Dimension {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (Dimension::Length(a), Dimension::Length(b)) => Dimension::Length(a.lerp(b, t)),
+            (Dimension::Percent(a), Dimension::Percent(b)) => Dimension::Percent(a.lerp(b, t)),
+            _ => snap(self, other, t),
+        }
+    }
+}
+impl Lerp for LengthPercentage {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (LengthPercentage::Length(a), LengthPercentage::Length(b)) => {
+                LengthPercentage::Length(a.lerp(b, t))
+            }
+            (LengthPercentage::Percent(a), LengthPercentage::Percent(b)) => {
+                LengthPercentage::Percent(a.lerp(b, t))
+            }
+            _ => snap(self, other, t),
+        }
+    }
+}
+impl Lerp for LengthPercentageAuto {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        match (self, other) {
+            (LengthPercentageAuto::Length(a), LengthPercentageAuto::Length(b)) => {
+                LengthPercentageAuto::Length(a.lerp(b, t))
+            }
+            (LengthPercentageAuto::Percent(a), LengthPercentageAuto::Percent(b)) => {
+                LengthPercentageAuto::Percent(a.lerp(b, t))
+            }
+            _ => snap(self, other, t),
+        }
+    }
+}
+impl<T: Lerp> Lerp for Size<T> {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Size {
+            width: self.width.lerp(&other.width, t),
+            height: self.height.lerp(&other.height, t),
+        }
+    }
+}
+impl<T: Lerp> Lerp for Rect<T> {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        Rect {
+            left: self.left.lerp(&other.left, t),
+            right: self.right.lerp(&other.right, t),
+            top: self.top.lerp(&other.top, t),
+            bottom: self.bottom.lerp(&other.bottom, t),
+        }
+    }
+}
+
+/// How far the nodes move in each step of the timeline.
+#[derive(Debug, Clone)]
+pub struct MotionAnalysis {
+    /// The duration of a step, in seconds.
+    pub step: f32,
+    /// The largest movement (of the location and size) of any node in each step.
+    pub movements: Vec<f32>,
+    pub jumps: Vec<Jump>,
+}
+
+/// A discontinuous change of the layout, like a flex item wrapping onto the next line.
+#[derive(Debug, Clone)]
+pub struct Jump {
+    /// The end of the step in which the node jumped.
+    pub time: f32,
+    /// The node that jumped the furthest.
+    pub node: NodeId,
+    pub distance: f32,
+    /// The number of other nodes that jumped in the same step.
+    pub other_nodes: usize,
+}
+
+/// The playback controls, the keyframes of `node`, and the jumps of the last analysis.
+/// Clicking a jump sets the time to it, and `clicked` to its node. The nodes are shown with `node_label`. Returns true if "find jumps" was clicked.
+pub fn timeline_ui(
+    ui: &mut egui::Ui,
+    tree: &mut TaffyTree,
+    node: NodeId,
+    timeline: &mut Timeline,
+    analysis: &Option<MotionAnalysis>,
+    node_label: impl Fn(NodeId) -> String,
+    clicked: &mut Option<NodeId>,
+) -> bool {
+    ui.checkbox(&mut timeline.enabled, "show the animation in Node Visuals")
+        .on_hover_text(
+            "a copy of the tree with the interpolated styles is laid out whenever the time changes",
+        );
+    ui.horizontal(|ui| {
+        let play = if timeline.playing { "⏸" } else { "▶" };
+        if ui.button(play).clicked() {
+            timeline.playing = !timeline.playing;
+            timeline.enabled = true;
+            if timeline.time >= timeline.duration {
+                timeline.time = 0.0;
+            }
+        }
+        if ui.button("⏮").on_hover_text("back to the start").clicked() {
+            timeline.time = 0.0;
+        }
+        ui.checkbox(&mut timeline.looping, "loop");
+        ui.label("duration");
+        ui.add(
+            egui::DragValue::new(&mut timeline.duration)
+                .range(0.1..=60.0)
+                .speed(0.1)
+                .suffix("s"),
+        );
+    });
+    ui.add(
+        egui::Slider::new(&mut timeline.time, 0.0..=timeline.duration)
+            .suffix("s")
+            .text("time"),
+    );
+    if let Some(analysis) = analysis {
+        motion_strip_ui(ui, timeline, analysis);
+    }
+    ui.separator();
+    ui.label(format!("keyframes of {}", node_label(node)));
+    let mut remove = None;
+    for (index, keyframe) in timeline
+        .keyframes
+        .get(&node)
+        .into_iter()
+        .flatten()
+        .enumerate()
+    {
+        ui.horizontal(|ui| {
+            ui.label(format!("{:.2}s", keyframe.time));
+            if ui.small_button("go to").clicked() {
+                timeline.time = keyframe.time;
+            }
+            if ui
+                .small_button("load")
+                .on_hover_text(
+                    "sets the node's style to this keyframe, to edit it and add it again",
+                )
+                .clicked()
+            {
+                tree.set_style(node, keyframe.style.clone()).unwrap();
+            }
+            if ui.small_button("🗑").clicked() {
+                remove = Some(index);
+            }
+        });
+    }
+    if let Some(index) = remove {
+        timeline.remove_keyframe(node, index);
+    }
+    if ui
+        .button(format!("set keyframe at {:.2}s", timeline.time))
+        .on_hover_text("uses the node's current style. add at least two keyframes at different times to animate the node")
+        .clicked()
+    {
+        if let Ok(style) = tree.style(node) {
+            timeline.set_keyframe(node, timeline.time, style.clone());
+        }
+    }
+    ui.weak(format!("{} animated nodes", timeline.keyframes.len()));
+    ui.separator();
+    let find = ui
+        .horizontal(|ui| {
+            let find = ui
+                .button("find jumps")
+                .on_hover_text(format!(
+                    "lays out a copy of the tree at {ANALYSIS_STEPS} steps of the timeline, and finds the nodes that move discontinuously"
+                ))
+                .clicked();
+            ui.label("threshold");
+            ui.add(
+                egui::DragValue::new(&mut timeline.jump_threshold)
+                    .range(1.0..=1000.0)
+                    .suffix("px"),
+            );
+            find
+        })
+        .inner;
+    if let Some(analysis) = analysis {
+        if analysis.jumps.is_empty() {
+            ui.weak("no jumps");
+        }
+        for jump in &analysis.jumps {
+            let mut text = format!(
+                "{:.2}s: {} jumps by {:.0}px",
+                jump.time,
+                node_label(jump.node),
+                jump.distance
+            );
+            if jump.other_nodes > 0 {
+                text += &format!(" (and {} other nodes)", jump.other_nodes);
+            }
+            if ui.selectable_label(false, text).clicked() {
+                timeline.enabled = true;
+                timeline.playing = false;
+                timeline.time = jump.time;
+                *clicked = Some(jump.node);
+            }
+        }
+    }
+    find
+}
+/// The largest movement in each step as bars (jumps in red), with the current time. Click to set the time.
+fn motion_strip_ui(ui: &mut egui::Ui, timeline: &mut Timeline, analysis: &MotionAnalysis) {
+    const HEIGHT: f32 = 40.0;
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(ui.available_width(), HEIGHT),
+        egui::Sense::click_and_drag(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+    let max = analysis.movements.iter().copied().fold(1.0, f32::max);
+    let bar_width = rect.width() / analysis.movements.len().max(1) as f32;
+    for (index, &movement) in analysis.movements.iter().enumerate() {
+        let time = analysis.step * (index + 1) as f32;
+        let jumped = analysis
+            .jumps
+            .iter()
+            .any(|jump| (jump.time - time).abs() < analysis.step / 2.0);
+        let color = if jumped {
+            Color32::RED
+        } else {
+            ui.visuals().weak_text_color()
+        };
+        let x = rect.left() + bar_width * index as f32;
+        let bar = egui::Rect::from_min_max(
+            egui::pos2(x, rect.bottom() - movement / max * HEIGHT),
+            egui::pos2(x + bar_width.max(1.0), rect.bottom()),
+        );
+        painter.rect_filled(bar, 0.0, color);
+    }
+    let x = rect.left() + rect.width() * timeline.time / timeline.duration.max(f32::EPSILON);
+    painter.vline(
+        x,
+        rect.y_range(),
+        Stroke::new(1.0_f32, ui.visuals().strong_text_color()),
+    );
+    if let Some(pos) = response.interact_pointer_pos() {
+        timeline.time = ((pos.x - rect.left()) / rect.width()).clamp(0.0, 1.0) * timeline.duration;
+        timeline.playing = false;
+    }
+    response.on_hover_text(format!(
+        "the largest movement of any node in each step (max {max:.0}px), jumps in red. click to set the time"
+    ));
+}
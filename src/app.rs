@@ -1,9 +1,32 @@
+use std::collections::HashMap;
+
+use crate::animation::{timeline_ui, AnimatedTree, MotionAnalysis, Timeline};
+use crate::benchmark::{benchmark_ui, run_benchmark, BenchmarkReport, BenchmarkSettings};
+use crate::css::{css_text_ui, CssValue};
+use crate::document::Document;
+use crate::fuzz::{fuzz, fuzz_ui, FuzzFailure, FuzzSettings};
+use crate::gallery::GALLERY;
+use crate::layout_trace::{cache_stats_ui, explain_size_ui, LayoutTrace};
+use crate::lint::{LintCache, LintWarning};
+use crate::paint_settings::{paint_settings_ui, FillMode, PaintSettings, Palette};
+use crate::responsive::{
+    filmstrip_ui, find_breakpoints, responsive_ui, Breakpoint, Filmstrip, ResponsiveSettings,
+};
+use crate::rounding::{subpixel_nodes, unrounded_layouts, RoundingError};
+use crate::selection::{select, SelectMode};
+use crate::stress::{stress_settings_ui, StressSettings};
+use crate::style_class::StyleClasses;
+use crate::style_docs::field_help_ui;
+use crate::style_field::{set_style_if_changed, StyleField, StyleSection};
+use crate::style_variant::{editing_variant_ui, style_variants_ui, StyleVariants};
 use egui::{Color32, ComboBox, DragValue, Painter, Sense, SidePanel, Stroke, UiBuilder, Vec2};
 use taffy::{
     prelude::TaffyZero, AlignContent, AlignItems, AlignSelf, BoxSizing, Dimension, FlexDirection,
-    LengthPercentage, LengthPercentageAuto, MaxTrackSizingFunction, MinTrackSizingFunction, NodeId,
-    PrintTree, Size, Style, TaffyTree, TextAlign, TraversePartialTree,
+    GridAutoFlow, LengthPercentage, LengthPercentageAuto, MaxTrackSizingFunction,
+    MinTrackSizingFunction, NodeId, Overflow, PrintTree, Size, Style, TaffyTree, TextAlign,
+    TraversePartialTree,
 };
+use web_time::{Duration, Instant};
 
 #[derive(Default, Debug)]
 pub struct TemplateApp {
@@ -15,7 +38,70 @@ pub struct TaffyEditor {
     tree: TaffyTree,
     root: taffy::NodeId,
     current_value: NodeId,
+    /// The nodes that are selected along with `current_value` (ctrl/shift + click). Style edits apply to all of them.
+    selected_nodes: Vec<NodeId>,
     default_style: Style,
+    /// scroll offsets of the scroll containers (nodes with [`taffy::Overflow::Scroll`]).
+    scroll_offsets: HashMap<NodeId, Vec2>,
+    /// paint the would-be position of [`taffy::Display::None`] nodes as faint outlines.
+    show_hidden_ghosts: bool,
+    /// The [`ghost_tree`], and the layout run (and animated layout) it was laid out for.
+    hidden_ghost: Option<((usize, Option<usize>), TaffyTree)>,
+    paint_settings: PaintSettings,
+    show_paint_settings: bool,
+    style_classes: StyleClasses,
+    show_style_classes: bool,
+    /// The styles of the nodes for ranges of the available width.
+    style_variants: StyleVariants,
+    /// The class that is being edited in the Style Classes window.
+    selected_class: Option<String>,
+    /// Used for creating or renaming classes.
+    new_class_name: String,
+    show_default_style: bool,
+    show_document_json: bool,
+    /// Shows how the size of the selected node was derived, from a trace of the layout calls.
+    show_explain_size: bool,
+    /// Shows the cache hits and sizing modes of the layout calls of each node.
+    show_layout_cache: bool,
+    /// Nodes that are computed more times than this in a layout pass are highlighted in the Layout Cache window and Node Visuals.
+    excessive_measure_threshold: usize,
+    /// The last layout of the tree. Layout only runs again when the tree is dirty.
+    last_layout: Option<LayoutRun>,
+    /// The trace of the tree's own layout passes, for the Layout Cache window.
+    layout_cache_trace: IncrementalTrace,
+    /// A full layout pass from empty caches for the Explain Size window, and the layout run it was recorded after.
+    explain_size_trace: Option<(usize, LayoutTrace)>,
+    /// The lint warnings of the nodes shown in the tree panel and the style editor.
+    lint_cache: LintCache,
+    show_stress_generator: bool,
+    stress_settings: StressSettings,
+    show_benchmark: bool,
+    benchmark_settings: BenchmarkSettings,
+    benchmark_report: Option<BenchmarkReport>,
+    show_fuzz: bool,
+    fuzz_settings: FuzzSettings,
+    /// The minimized failures of the fuzzer, saved across restarts.
+    fuzz_failures: Vec<FuzzFailure>,
+    /// How many frames the tree (or the style variants) was edited in, to know when the copies of the tree are stale.
+    tree_edits: usize,
+    show_responsive: bool,
+    responsive: ResponsiveSettings,
+    breakpoints: Vec<Breakpoint>,
+    filmstrip: Option<Filmstrip>,
+    show_animation: bool,
+    timeline: Timeline,
+    /// The tree shown in Node Visuals while the animation is enabled.
+    animated_tree: Option<AnimatedTree>,
+    motion_analysis: Option<MotionAnalysis>,
+    /// [`TaffyTree::enable_rounding`] or [`TaffyTree::disable_rounding`].
+    rounding: bool,
+    /// Outlines the nodes whose edges rounding moved by more than half a pixel.
+    show_subpixel_overlay: bool,
+    /// The text of the Document JSON window, for exporting/importing documents.
+    document_json: String,
+    /// The error from the last import of `document_json`.
+    document_json_error: Option<String>,
+    style_filter: StyleFilter,
 }
 impl Default for TaffyEditor {
     fn default() -> Self {
@@ -82,63 +168,377 @@ impl Default for TaffyEditor {
             default_style,
             root,
             current_value: root,
+            selected_nodes: vec![],
+            scroll_offsets: HashMap::new(),
+            show_hidden_ghosts: false,
+            hidden_ghost: None,
+            paint_settings: PaintSettings::default(),
+            show_paint_settings: false,
+            style_classes: StyleClasses::default(),
+            show_style_classes: false,
+            style_variants: StyleVariants::default(),
+            selected_class: None,
+            new_class_name: String::new(),
+            show_default_style: false,
+            show_document_json: false,
+            show_explain_size: false,
+            show_layout_cache: false,
+            excessive_measure_threshold: 4,
+            last_layout: None,
+            layout_cache_trace: IncrementalTrace::default(),
+            explain_size_trace: None,
+            lint_cache: LintCache::default(),
+            show_stress_generator: false,
+            stress_settings: StressSettings::default(),
+            show_benchmark: false,
+            benchmark_settings: BenchmarkSettings::default(),
+            benchmark_report: None,
+            show_fuzz: false,
+            fuzz_settings: FuzzSettings::default(),
+            fuzz_failures: vec![],
+            tree_edits: 0,
+            show_responsive: false,
+            responsive: ResponsiveSettings::default(),
+            breakpoints: vec![],
+            filmstrip: None,
+            show_animation: false,
+            timeline: Timeline::default(),
+            animated_tree: None,
+            motion_analysis: None,
+            rounding: true,
+            show_subpixel_overlay: false,
+            document_json: String::new(),
+            document_json_error: None,
+            style_filter: StyleFilter::default(),
         }
     }
 }
 impl TaffyEditor {
+    /// Replaces the current tree with the nodes of the document.
+    pub fn load_document(&mut self, document: &Document) {
+        let (tree, root, style_classes, style_variants) = document.to_tree();
+        self.tree = tree;
+        self.root = root;
+        self.current_value = root;
+        self.selected_nodes.clear();
+        self.default_style = document.default_style.clone();
+        self.scroll_offsets.clear();
+        self.style_classes = style_classes;
+        self.style_variants = style_variants;
+        self.selected_class = None;
+        // they refer to the nodes of the old tree
+        self.breakpoints.clear();
+        self.timeline.keyframes.clear();
+        self.motion_analysis = None;
+        self.apply_rounding();
+    }
+    /// Enables or disables the rounding of the tree, and lays it out again.
+    fn apply_rounding(&mut self) {
+        if self.rounding {
+            self.tree.enable_rounding();
+        } else {
+            self.tree.disable_rounding();
+        }
+        self.tree.mark_dirty(self.root).unwrap();
+    }
+    /// Takes a snapshot of the current tree, along with the default style and style classes.
+    pub fn document(&self) -> Document {
+        Document::from_tree(
+            &self.tree,
+            self.root,
+            &self.default_style,
+            &self.style_classes,
+            &self.style_variants,
+        )
+    }
+    fn menu_bar_ui(&mut self, ui: &mut egui::Ui) {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button("View", |ui| {
+                ui.checkbox(&mut self.show_paint_settings, "Paint Settings");
+                ui.checkbox(&mut self.show_style_classes, "Style Classes");
+                ui.checkbox(&mut self.show_default_style, "Default Style");
+                ui.checkbox(&mut self.show_explain_size, "Explain Size");
+                ui.checkbox(&mut self.show_layout_cache, "Layout Cache");
+                ui.checkbox(&mut self.show_responsive, "Responsive Preview");
+                ui.checkbox(&mut self.show_animation, "Animation");
+                ui.separator();
+                if ui
+                    .checkbox(&mut self.rounding, "Round Layout")
+                    .on_hover_text("rounds the layout to whole pixels (taffy's default)")
+                    .changed()
+                {
+                    self.apply_rounding();
+                }
+                ui.add_enabled(
+                    self.rounding,
+                    egui::Checkbox::new(&mut self.show_subpixel_overlay, "Sub-pixel Overlay"),
+                )
+                .on_hover_text(
+                    "outlines the nodes whose edges rounding moved by more than half a pixel, and where they would be without rounding",
+                );
+            });
+            ui.menu_button("Document", |ui| {
+                if ui.button("Export / Import JSON").clicked() {
+                    self.document_json =
+                        serde_json::to_string_pretty(&self.document()).unwrap_or_default();
+                    self.document_json_error = None;
+                    self.show_document_json = true;
+                    ui.close_menu();
+                }
+                if ui.button("Generate Stress Tree").clicked() {
+                    self.show_stress_generator = true;
+                    ui.close_menu();
+                }
+                if ui.button("Benchmark").clicked() {
+                    self.show_benchmark = true;
+                    ui.close_menu();
+                }
+                // panics can't be caught on the web, so the fuzzer would abort the app
+                #[cfg(not(target_arch = "wasm32"))]
+                if ui.button("Fuzz").clicked() {
+                    self.show_fuzz = true;
+                    ui.close_menu();
+                }
+            });
+            ui.menu_button("Gallery", |ui| {
+                ui.set_max_width(300.0);
+                for entry in GALLERY {
+                    if ui.button(entry.name).clicked() {
+                        self.load_document(&(entry.build)());
+                        ui.close_menu();
+                    }
+                    ui.label(egui::RichText::new(entry.description).small().weak());
+                    ui.separator();
+                }
+            });
+        });
+    }
+    /// The Document JSON window, to copy the current document or to load a document from json.
+    fn document_json_ui(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_document_json;
+        egui::Window::new("Document JSON")
+            .open(&mut open)
+            .default_size([400.0, 500.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui
+                        .button("refresh")
+                        .on_hover_text("replaces the text with the current document")
+                        .clicked()
+                    {
+                        self.document_json =
+                            serde_json::to_string_pretty(&self.document()).unwrap_or_default();
+                        self.document_json_error = None;
+                    }
+                    if ui.button("📋 copy").clicked() {
+                        ui.ctx().copy_text(self.document_json.clone());
+                    }
+                    if ui
+                        .button("load")
+                        .on_hover_text("replaces the current document with the json")
+                        .clicked()
+                    {
+                        match serde_json::from_str::<Document>(&self.document_json) {
+                            Ok(document) => {
+                                self.load_document(&document);
+                                self.document_json_error = None;
+                            }
+                            Err(e) => self.document_json_error = Some(e.to_string()),
+                        }
+                    }
+                });
+                if let Some(error) = &self.document_json_error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.add(
+                        egui::TextEdit::multiline(&mut self.document_json)
+                            .code_editor()
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+            });
+        self.show_document_json = open;
+    }
+    /// The Stress Tree Generator window, to replace the document with a large random tree.
+    fn stress_generator_ui(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_stress_generator;
+        let mut generate = false;
+        egui::Window::new("Stress Tree Generator")
+            .open(&mut open)
+            .show(ctx, |ui| {
+                generate = stress_settings_ui(ui, &mut self.stress_settings);
+            });
+        if generate {
+            self.load_document(&self.stress_settings.generate());
+        }
+        self.show_stress_generator = open;
+    }
+    /// The Benchmark window, to time the layout of the current document.
+    fn benchmark_window_ui(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_benchmark;
+        let mut run = false;
+        egui::Window::new("Benchmark")
+            .open(&mut open)
+            .scroll([false, true])
+            .show(ctx, |ui| {
+                run = benchmark_ui(ui, &mut self.benchmark_settings, &self.benchmark_report);
+            });
+        if run {
+            self.benchmark_report = Some(run_benchmark(&self.document(), &self.benchmark_settings));
+        }
+        self.show_benchmark = open;
+    }
+    /// The Fuzz window, to find styles that break the layout.
+    fn fuzz_window_ui(&mut self, ctx: &egui::Context) {
+        let mut open = self.show_fuzz;
+        let mut run = false;
+        let mut load = None;
+        egui::Window::new("Fuzz")
+            .open(&mut open)
+            .scroll([false, true])
+            .show(ctx, |ui| {
+                run = fuzz_ui(
+                    ui,
+                    &mut self.fuzz_settings,
+                    &mut self.fuzz_failures,
+                    &mut load,
+                );
+            });
+        if run {
+            let document = if self.fuzz_settings.use_stress_tree {
+                self.stress_settings.generate()
+            } else {
+                self.document()
+            };
+            let failures = fuzz(&document, &self.fuzz_settings);
+            self.fuzz_failures.extend(failures);
+        }
+        if let Some(document) = load {
+            self.load_document(&document);
+        }
+        self.show_fuzz = open;
+    }
     pub fn ui(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("menu bar").show(ctx, |ui| {
+            self.menu_bar_ui(ui);
+        });
+        self.document_json_ui(ctx);
+        self.stress_generator_ui(ctx);
+        self.benchmark_window_ui(ctx);
+        self.fuzz_window_ui(ctx);
         let Self {
             tree,
             root,
             current_value,
+            selected_nodes,
             default_style,
+            scroll_offsets,
+            show_hidden_ghosts,
+            hidden_ghost,
+            paint_settings,
+            show_paint_settings,
+            style_classes,
+            show_style_classes,
+            style_variants,
+            selected_class,
+            new_class_name,
+            show_default_style,
+            show_document_json: _,
+            show_explain_size,
+            show_layout_cache,
+            excessive_measure_threshold,
+            last_layout,
+            layout_cache_trace,
+            explain_size_trace,
+            lint_cache,
+            show_stress_generator: _,
+            stress_settings: _,
+            show_benchmark: _,
+            benchmark_settings: _,
+            benchmark_report: _,
+            show_fuzz: _,
+            fuzz_settings: _,
+            fuzz_failures: _,
+            tree_edits,
+            show_responsive,
+            responsive,
+            breakpoints,
+            filmstrip,
+            show_animation,
+            timeline,
+            animated_tree,
+            motion_analysis,
+            rounding,
+            show_subpixel_overlay,
+            document_json: _,
+            document_json_error: _,
+            style_filter,
         } = self;
         let root = *root;
-        egui::Window::new("Node Visuals")
-            .default_size([600.0, 400.0])
+        let variants_version = style_variants.version;
+        lint_cache.invalidate(last_layout.map_or(0, |run| run.runs));
+        let palette = paint_settings.palette(ctx.style().visuals.dark_mode);
+        egui::Window::new("Default Style")
+            .open(show_default_style)
+            .scroll([false, true])
             .show(ctx, |ui| {
-                let layout = *tree.get_final_layout(root);
-                ui.scope_builder(
-                    UiBuilder::new()
-                        .id_salt("node painter")
-                        .sense(Sense::click()),
-                    |ui| {
-                        ui.set_min_size(egui::vec2(layout.size.width, layout.size.height));
-                        let res = ui.response();
-                        let offset = res.rect.min;
-                        let offset = egui::vec2(offset.x, offset.y);
-
-                        if let Some(pos) = res.hover_pos() {
-                            if let Some(hover_node) = node_event_recursive(
-                                tree,
-                                NodeEvent::Hover(egui::vec2(pos.x, pos.y)),
-                                offset,
-                                root,
-                            ) {
-                                let hover_layout = *tree.get_final_layout(hover_node);
-                                res.clone().on_hover_text(format!("{:#?}", hover_layout));
-                            }
-                        }
-                        if let Some(pos) = res.interact_pointer_pos() {
-                            if let Some(click_node) = node_event_recursive(
-                                tree,
-                                NodeEvent::Click(egui::vec2(pos.x, pos.y)),
-                                offset,
-                                root,
-                            ) {
-                                *current_value = click_node;
-                            }
-                        }
-                        node_tree_paint_recursive(tree, root, ui.painter(), offset, *current_value);
+                ui.label("new nodes (add child) use this style, and reset style resets to it.");
+                if ui.button("use selected node's style as default").clicked() {
+                    if let Ok(style) = tree.style(*current_value) {
+                        *default_style = style.clone();
+                    }
+                }
+                style_editor_grid(
+                    ui,
+                    "default style editor",
+                    default_style,
+                    |_| true,
+                    |ui, field| {
+                        ui.horizontal(|ui| {
+                            ui.label(field.name());
+                            field_help_ui(ui, field);
+                        });
                     },
                 );
             });
+        egui::Window::new("Style Classes")
+            .open(show_style_classes)
+            .scroll([false, true])
+            .show(ctx, |ui| {
+                style_classes_ui(
+                    ui,
+                    tree,
+                    *current_value,
+                    style_classes,
+                    selected_class,
+                    new_class_name,
+                );
+            });
         egui::Window::new("Node Editor")
             .default_size([600.0, 400.0])
             .scroll([true, true])
             .show(ctx, |ui| {
                 SidePanel::left("node selector").show_inside(ui, |ui| {
-                    node_tree_ui_recursive(ui, tree, root, current_value);
+                    let mut clicked = None;
+                    let mut options = NodeTreeUiOptions {
+                        focused_node: *current_value,
+                        selected_nodes,
+                        style_classes,
+                        style_variants,
+                        lint_cache,
+                    };
+                    node_tree_ui(ui, tree, root, &mut options, &mut clicked);
+                    if let Some(node) = clicked {
+                        select(
+                            tree,
+                            root,
+                            current_value,
+                            selected_nodes,
+                            node,
+                            SelectMode::from_modifiers(ui.input(|i| i.modifiers)),
+                        );
+                    }
                 });
                 ui.indent("style editor indent", |ui| {
                     const GIT_HASH: &str = env!("VERGEN_GIT_SHA");
@@ -151,15 +551,44 @@ impl TaffyEditor {
                         }
                         ui.add_enabled_ui(*current_value != root, |ui| {
                             if ui.button("delete node ").clicked() {
-                                let new_current_value = tree.parent(*current_value).unwrap_or(root);
-                                let _ = tree.remove(*current_value);
-                                *current_value = new_current_value;
+                                let parent = tree.parent(*current_value).unwrap_or(root);
+                                // taffy only removes the node itself, so the whole subtree is removed here
+                                let mut removed = vec![*current_value];
+                                let mut i = 0;
+                                while i < removed.len() {
+                                    removed.extend(tree.child_ids(removed[i]));
+                                    i += 1;
+                                }
+                                for &node in &removed {
+                                    let _ = tree.remove(node);
+                                    style_classes.detach(node);
+                                    style_variants.detach(node);
+                                    timeline.detach(node);
+                                    scroll_offsets.remove(&node);
+                                }
+                                selected_nodes.retain(|node| !removed.contains(node));
+                                *current_value = parent;
                             }
                         });
 
-                        if ui.button("reset style").clicked() {
-                            tree.set_style(*current_value, default_style.clone())
-                                .unwrap();
+                        let res = ui.button("reset style");
+                        if res.clicked() {
+                            for &node in std::iter::once(&*current_value).chain(selected_nodes.iter()) {
+                                match style_classes.members.get(&node) {
+                                    Some(membership) => {
+                                        let class = membership.class.clone();
+                                        style_classes.apply(tree, node, &class);
+                                    }
+                                    None => {
+                                        tree.set_style(node, default_style.clone()).unwrap();
+                                    }
+                                }
+                            }
+                        }
+                        if res.hovered() {
+                            res.on_hover_text(
+                                "resets the selected nodes to the default style, or to the class style (discarding overrides) if the node uses a class",
+                            );
                         }
                         let res = ui.button("print tree");
                         if res.clicked() {
@@ -171,437 +600,1342 @@ impl TaffyEditor {
                             );
                         }
                     });
-                    taffy_style_editor(ui, tree, *current_value)
+                    editing_variant_ui(ui, *current_value, style_variants, responsive);
+                    ui.collapsing("breakpoints", |ui| {
+                        style_variants_ui(ui, tree, *current_value, style_variants, responsive);
+                    });
+                    taffy_style_editor(
+                        ui,
+                        tree,
+                        *current_value,
+                        selected_nodes,
+                        StyleEditorState {
+                            style_classes,
+                            style_variants,
+                            filter: style_filter,
+                            default_style,
+                            lint_cache,
+                        },
+                    )
                 });
             });
-        tree.compute_layout(
+        // edits happen before the layout, and everything that shows the layout after it, so that it's never stale.
+        egui::Window::new("Responsive Preview")
+            .open(show_responsive)
+            .show(ctx, |ui| {
+                if let taffy::Dimension::Length(width) = tree.style(root).unwrap().size.width {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        format!("the root has a width of {width}px, so it doesn't reflow with the available width"),
+                    );
+                }
+                let mut clicked = None;
+                let label = |node| node_label(node, style_classes, style_variants);
+                if responsive_ui(ui, responsive, breakpoints, label, &mut clicked) {
+                    *breakpoints = find_breakpoints(tree, root, responsive, style_variants);
+                }
+                if let Some(node) = clicked {
+                    select(
+                        tree,
+                        root,
+                        current_value,
+                        selected_nodes,
+                        node,
+                        SelectMode::Replace,
+                    );
+                }
+            });
+        if responsive.enabled && responsive.animate {
+            responsive.animate(ctx.input(|i| i.stable_dt));
+            ctx.request_repaint();
+        }
+        let available_space = responsive.available_space().unwrap_or(ROOT_AVAILABLE_SPACE);
+        egui::Window::new("Animation")
+            .open(show_animation)
+            .show(ctx, |ui| {
+                let mut clicked = None;
+                let label = |node| node_label(node, style_classes, style_variants);
+                if timeline_ui(
+                    ui,
+                    tree,
+                    *current_value,
+                    timeline,
+                    motion_analysis,
+                    label,
+                    &mut clicked,
+                ) {
+                    *motion_analysis = Some(timeline.analyze(tree, root, available_space));
+                }
+                if let Some(node) = clicked {
+                    select(
+                        tree,
+                        root,
+                        current_value,
+                        selected_nodes,
+                        node,
+                        SelectMode::Replace,
+                    );
+                }
+            });
+        if timeline.enabled && timeline.playing {
+            timeline.advance(ctx.input(|i| i.stable_dt));
+            ctx.request_repaint();
+        }
+        // the tree is only dirty here after edits made earlier in this frame, as it was laid out below in the previous one
+        if tree.dirty(root).unwrap_or(true) || style_variants.version != variants_version {
+            *tree_edits += 1;
+            // they were found in the tree before the edit
+            breakpoints.clear();
+        }
+        // the styles that the variants switch to aren't edits
+        style_variants.apply(tree, responsive.enabled.then_some(responsive.width));
+        if !*show_layout_cache {
+            // the mirrored caches go stale when the layouts aren't traced
+            *layout_cache_trace = IncrementalTrace::default();
+        } else if layout_cache_trace.last.is_none() && layout_cache_trace.caches.is_empty() {
+            // the window was just opened: start both the tree and the mirror from empty caches
+            mark_subtree_dirty(tree, root);
+        }
+        layout_if_dirty(
+            tree,
             root,
-            Size {
-                width: taffy::AvailableSpace::MinContent,
-                height: taffy::AvailableSpace::MinContent,
-            },
-        )
-        .unwrap();
+            available_space,
+            last_layout,
+            show_layout_cache.then_some(layout_cache_trace),
+        );
+        if timeline.enabled && !timeline.keyframes.is_empty() {
+            timeline.update_animated_tree(animated_tree, tree, root, available_space, *tree_edits);
+        } else {
+            *animated_tree = None;
+        }
+        let animated_layouts = animated_tree.as_ref().map(|animated| animated.layouts);
+        let mut animated = animated_tree.as_mut().map(|animated| &mut animated.tree);
+        // of the focused node and its ancestors for the inspector
+        let unrounded = rounding.then(|| {
+            let nodes: Vec<NodeId> =
+                std::iter::successors(Some(*current_value), |&node| tree.parent(node)).collect();
+            unrounded_layouts(tree, nodes)
+        });
+        // of the tree shown in Node Visuals
+        let subpixel: HashMap<NodeId, RoundingError> = if *rounding && *show_subpixel_overlay {
+            let visual_tree = animated.as_deref_mut().unwrap_or(&mut *tree);
+            let nodes: Vec<NodeId> = node_tree_rows(visual_tree, root)
+                .into_iter()
+                .map(|row| row.node_id)
+                .collect();
+            let layouts = unrounded_layouts(visual_tree, nodes);
+            subpixel_nodes(visual_tree, root, &layouts)
+        } else {
+            HashMap::new()
+        };
+        egui::TopBottomPanel::bottom("status bar").show(ctx, |ui| {
+            status_bar_ui(ui, last_layout);
+        });
+        egui::SidePanel::right("layout inspector")
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.heading("Layout Inspector");
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    crate::inspector::layout_inspector_ui(
+                        ui,
+                        tree,
+                        *current_value,
+                        &palette,
+                        unrounded.as_ref(),
+                    );
+                });
+            });
+        egui::Window::new("Paint Settings")
+            .open(show_paint_settings)
+            .show(ctx, |ui| {
+                paint_settings_ui(ui, paint_settings);
+            });
+        // a full layout pass from empty caches, so every step is in the trace. recorded again after each layout
+        let runs = last_layout.map_or(0, |run| run.runs);
+        if !*show_explain_size {
+            *explain_size_trace = None;
+        } else if explain_size_trace
+            .as_ref()
+            .map_or(true, |(traced_runs, _)| *traced_runs != runs)
+        {
+            *explain_size_trace = Some((runs, LayoutTrace::record(tree, root, available_space)));
+        }
+        egui::Window::new("Explain Size")
+            .open(show_explain_size)
+            .default_width(400.0)
+            .scroll([false, true])
+            .show(ctx, |ui| {
+                if let Some((_, trace)) = explain_size_trace {
+                    explain_size_ui(ui, tree, *current_value, trace);
+                }
+            });
+        let hot_nodes: Vec<NodeId> = if let Some(trace) = &layout_cache_trace.last {
+            trace
+                .cache_stats()
+                .into_iter()
+                .filter(|(_, stats)| stats.computed() > *excessive_measure_threshold)
+                .map(|(node, _)| node)
+                .collect()
+        } else {
+            vec![]
+        };
+        egui::Window::new("Layout Cache")
+            .open(show_layout_cache)
+            .scroll([true, true])
+            .show(ctx, |ui| {
+                let Some(trace) = &layout_cache_trace.last else {
+                    return;
+                };
+                ui.label(format!(
+                    "the layout calls of the last layout pass of the tree (run {}). \
+                    the first pass after opening this window starts from empty caches, \
+                    the later ones only recompute what the edits made dirty.",
+                    layout_cache_trace.run
+                ));
+                let mut clicked = None;
+                cache_stats_ui(
+                    ui,
+                    tree,
+                    root,
+                    trace,
+                    excessive_measure_threshold,
+                    &mut clicked,
+                );
+                if let Some(node) = clicked {
+                    select(
+                        tree,
+                        root,
+                        current_value,
+                        selected_nodes,
+                        node,
+                        SelectMode::from_modifiers(ui.input(|i| i.modifiers)),
+                    );
+                }
+            });
+        egui::Window::new("Node Visuals")
+            .default_size([600.0, 400.0])
+            .scroll([true, true])
+            .show(ctx, |ui| {
+                ui.checkbox(show_hidden_ghosts, "show hidden nodes")
+                    .on_hover_text(
+                        "outlines where the `display: None` nodes would be if they were displayed",
+                    );
+                if responsive.enabled && responsive.filmstrip {
+                    if filmstrip
+                        .as_ref()
+                        .map_or(true, |filmstrip| filmstrip.is_stale(responsive, *tree_edits))
+                    {
+                        *filmstrip = Some(Filmstrip::new(
+                            tree,
+                            root,
+                            responsive,
+                            style_variants,
+                            *tree_edits,
+                        ));
+                    }
+                    filmstrip_ui(
+                        ui,
+                        filmstrip.as_ref().unwrap(),
+                        responsive,
+                        paint_settings,
+                        &palette,
+                    );
+                    ui.separator();
+                } else {
+                    *filmstrip = None;
+                }
+                let tree: &TaffyTree = match animated.as_deref() {
+                    Some(animated) => {
+                        ui.weak(format!("showing the animation at {:.2}s", timeline.time));
+                        animated
+                    }
+                    None => tree,
+                };
+                let layout = *tree.get_final_layout(root);
+                ui.scope_builder(
+                    UiBuilder::new()
+                        .id_salt("node painter")
+                        .sense(Sense::click()),
+                    |ui| {
+                        ui.set_min_size(egui::vec2(layout.size.width, layout.size.height));
+                        let res = ui.response();
+                        let offset = res.rect.min;
+                        let offset = egui::vec2(offset.x, offset.y);
+
+                        if let Some(pos) = res.hover_pos() {
+                            if let Some(hover_node) = node_event_recursive(
+                                tree,
+                                NodeEvent::Hover(egui::vec2(pos.x, pos.y)),
+                                offset,
+                                root,
+                                scroll_offsets,
+                                egui::Rect::EVERYTHING,
+                            ) {
+                                let scroll_delta = ui.input(|i| i.smooth_scroll_delta);
+                                if scroll_delta != Vec2::ZERO
+                                    && scroll_recursive(
+                                        tree,
+                                        hover_node,
+                                        scroll_delta,
+                                        scroll_offsets,
+                                    )
+                                {
+                                    // so that the window doesn't scroll along with the node
+                                    ui.input_mut(|i| i.smooth_scroll_delta = Vec2::ZERO);
+                                }
+                                let hover_layout = *tree.get_final_layout(hover_node);
+                                let mut text = format!(
+                                    "{:?}: {} × {}\nclick to inspect, ctrl/shift + click to select multiple nodes",
+                                    hover_node, hover_layout.size.width, hover_layout.size.height
+                                );
+                                if let Some(error) = subpixel.get(&hover_node) {
+                                    text += &format!(
+                                        "\nrounding moved its edges by up to {:.2}px (white: without rounding)",
+                                        error.distance
+                                    );
+                                }
+                                res.clone().on_hover_text(text);
+                            }
+                        }
+                        if let Some(pos) = res.interact_pointer_pos().filter(|_| res.clicked()) {
+                            if let Some(click_node) = node_event_recursive(
+                                tree,
+                                NodeEvent::Click(egui::vec2(pos.x, pos.y)),
+                                offset,
+                                root,
+                                scroll_offsets,
+                                egui::Rect::EVERYTHING,
+                            ) {
+                                select(
+                                    tree,
+                                    root,
+                                    current_value,
+                                    selected_nodes,
+                                    click_node,
+                                    SelectMode::from_modifiers(ui.input(|i| i.modifiers)),
+                                );
+                            }
+                        }
+                        node_tree_paint_recursive(
+                            tree,
+                            root,
+                            ui.painter(),
+                            offset,
+                            0,
+                            &PaintOptions {
+                                focused_node: *current_value,
+                                selected_nodes,
+                                hot_nodes: &hot_nodes,
+                                subpixel: &subpixel,
+                                scroll_offsets,
+                                settings: paint_settings,
+                                palette,
+                            },
+                        );
+                        if *show_hidden_ghosts {
+                            // laid out again only when the tree that is shown was
+                            let key = (
+                                last_layout.map_or(0, |run| run.runs),
+                                animated_layouts,
+                            );
+                            if hidden_ghost.as_ref().map_or(true, |(ghost_key, _)| *ghost_key != key) {
+                                *hidden_ghost = Some((key, ghost_tree(tree, root, available_space)));
+                            }
+                            let (_, ghost) = hidden_ghost.as_ref().unwrap();
+                            ghost_paint_recursive(
+                                ghost,
+                                tree,
+                                root,
+                                ui.painter(),
+                                offset,
+                                false,
+                                scroll_offsets,
+                            );
+                        } else {
+                            *hidden_ghost = None;
+                        }
+                        absolute_guides_paint(
+                            tree,
+                            *current_value,
+                            ui.painter(),
+                            offset,
+                            scroll_offsets,
+                        );
+                        if let Some(width) = responsive.enabled.then_some(responsive.width) {
+                            // the edge of the available width of the root
+                            ui.painter().vline(
+                                offset.x + width,
+                                res.rect.y_range(),
+                                Stroke::new(1.0_f32, ui.visuals().warn_fg_color),
+                            );
+                        }
+                    },
+                );
+            });
     }
 }
+/// The available space that we use to lay out the root node.
+const ROOT_AVAILABLE_SPACE: Size<taffy::AvailableSpace> = Size {
+    width: taffy::AvailableSpace::MinContent,
+    height: taffy::AvailableSpace::MinContent,
+};
 
-fn node_tree_ui_recursive(
-    ui: &mut egui::Ui,
+/// The stats of a layout pass, shown in the status bar.
+#[derive(Debug, Clone, Copy)]
+struct LayoutRun {
+    available_space: Size<taffy::AvailableSpace>,
+    duration: Duration,
+    node_count: usize,
+    /// How many times the layout has run since the app started.
+    runs: usize,
+}
+/// The trace of the layout passes of the tree, with a mirror of its caches so the incremental passes can be traced.
+#[derive(Debug, Default)]
+struct IncrementalTrace {
+    caches: HashMap<NodeId, taffy::Cache>,
+    /// The trace of the last layout pass. Kept until the tree is laid out again.
+    last: Option<LayoutTrace>,
+    /// The [`LayoutRun::runs`] of the traced pass.
+    run: usize,
+}
+/// Lays out the tree, unless it wasn't edited and the available space is the same as in the last layout.
+/// With `trace`, the same pass is traced first, while the dirty nodes are still known.
+fn layout_if_dirty(
     tree: &mut TaffyTree,
-    node_id: taffy::NodeId,
-    current_selected_di: &mut taffy::NodeId,
+    root: NodeId,
+    available_space: Size<taffy::AvailableSpace>,
+    last_layout: &mut Option<LayoutRun>,
+    trace: Option<&mut IncrementalTrace>,
 ) {
-    ui.selectable_value(current_selected_di, node_id, format!("{:?}", node_id));
-    if tree.child_count(node_id) != 0 {
-        ui.indent(node_id, |ui| {
-            for child in tree.children(node_id).unwrap_or_default() {
-                node_tree_ui_recursive(ui, tree, child, current_selected_di);
+    let is_dirty = tree.dirty(root).unwrap_or(true);
+    if !is_dirty && last_layout.is_some_and(|run| run.available_space == available_space) {
+        return;
+    }
+    let runs = last_layout.map_or(0, |run| run.runs) + 1;
+    if let Some(trace) = trace {
+        trace.last = Some(LayoutTrace::record_incremental(
+            tree,
+            root,
+            available_space,
+            &mut trace.caches,
+        ));
+        trace.run = runs;
+    }
+    let start = Instant::now();
+    tree.compute_layout(root, available_space).unwrap();
+    *last_layout = Some(LayoutRun {
+        available_space,
+        duration: start.elapsed(),
+        node_count: tree.total_node_count(),
+        runs,
+    });
+}
+/// Clears the layout caches of every node below `root`, so the next layout starts from scratch.
+fn mark_subtree_dirty(tree: &mut TaffyTree, root: NodeId) {
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        tree.mark_dirty(node).unwrap();
+        stack.extend(tree.child_ids(node));
+    }
+}
+fn status_bar_ui(ui: &mut egui::Ui, last_layout: &Option<LayoutRun>) {
+    let Some(run) = last_layout else {
+        ui.label("not laid out yet");
+        return;
+    };
+    ui.horizontal(|ui| {
+        ui.label(format!(
+            "last layout: {:.3} ms for {} nodes",
+            run.duration.as_secs_f64() * 1000.0,
+            run.node_count
+        ));
+        ui.separator();
+        ui.label(format!("layout runs: {}", run.runs))
+            .on_hover_text("layout only runs after an edit (or a change of the available space)");
+    });
+}
+
+/// Everything that [`node_tree_ui`] needs, and doesn't change between the rows.
+struct NodeTreeUiOptions<'a> {
+    focused_node: NodeId,
+    selected_nodes: &'a [NodeId],
+    style_classes: &'a StyleClasses,
+    style_variants: &'a StyleVariants,
+    lint_cache: &'a mut LintCache,
+}
+fn lint_warnings_text(warnings: &[LintWarning]) -> String {
+    warnings
+        .iter()
+        .map(|warning| format!("⚠ {}", warning.message))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+/// A row of the tree panel.
+struct NodeTreeRow {
+    node_id: NodeId,
+    depth: usize,
+    /// true if the node or any of its ancestors is [`taffy::Display::None`].
+    hidden: bool,
+}
+/// All the nodes of the tree as rows of the tree panel, in tree order.
+fn node_tree_rows(tree: &TaffyTree, root: NodeId) -> Vec<NodeTreeRow> {
+    fn rows_recursive(
+        tree: &TaffyTree,
+        node_id: NodeId,
+        depth: usize,
+        hidden: bool,
+        rows: &mut Vec<NodeTreeRow>,
+    ) {
+        let hidden = hidden
+            || tree
+                .style(node_id)
+                .is_ok_and(|style| style.display == taffy::Display::None);
+        rows.push(NodeTreeRow {
+            node_id,
+            depth,
+            hidden,
+        });
+        for child in tree.child_ids(node_id) {
+            rows_recursive(tree, child, depth + 1, hidden, rows);
+        }
+    }
+    let mut rows = vec![];
+    rows_recursive(tree, root, 0, false, &mut rows);
+    rows
+}
+/// The tree panel. Only the rows that are scrolled into view are shown, so that it stays fast with thousands of nodes.
+/// `clicked` is set to the node that was clicked (if any), so that the caller can update the selection.
+fn node_tree_ui(
+    ui: &mut egui::Ui,
+    tree: &TaffyTree,
+    root: NodeId,
+    options: &mut NodeTreeUiOptions<'_>,
+    clicked: &mut Option<NodeId>,
+) {
+    let rows = node_tree_rows(tree, root);
+    let row_height = ui.spacing().interact_size.y;
+    // the editor window scrolls too, so the height has to be limited for the rows to be virtualized
+    egui::ScrollArea::vertical()
+        .id_salt("node tree rows")
+        .max_height(ui.ctx().screen_rect().height() * 0.6)
+        .show_rows(ui, row_height, rows.len(), |ui, range| {
+            for row in &rows[range] {
+                node_tree_row_ui(ui, tree, row, options, clicked);
             }
         });
+}
+/// The label of a node in the tree panel: its id, its class and its number of style variants.
+fn node_label(
+    node_id: NodeId,
+    style_classes: &StyleClasses,
+    style_variants: &StyleVariants,
+) -> String {
+    let mut text = format!("{:?}", node_id);
+    if let Some(membership) = style_classes.members.get(&node_id) {
+        text += &format!(" .{}", membership.class);
+    }
+    if let Some(node_variants) = style_variants.nodes.get(&node_id) {
+        text += &format!(" @{}", node_variants.variants.len());
+    }
+    text
+}
+/// Hidden nodes are greyed out, and the `Display::None` nodes themselves get a marker.
+/// Nodes with lint warnings get a ⚠ marker, with the warnings as its tooltip.
+fn node_tree_row_ui(
+    ui: &mut egui::Ui,
+    tree: &TaffyTree,
+    row: &NodeTreeRow,
+    options: &mut NodeTreeUiOptions<'_>,
+    clicked: &mut Option<NodeId>,
+) {
+    let node_id = row.node_id;
+    let display_none = tree
+        .style(node_id)
+        .is_ok_and(|style| style.display == taffy::Display::None);
+    let mut text = node_label(node_id, options.style_classes, options.style_variants);
+    if display_none {
+        text += " ⊘ none";
+    }
+    let mut text = egui::RichText::new(text);
+    if row.hidden {
+        text = text.weak().italics();
     }
+    let selected = options.focused_node == node_id || options.selected_nodes.contains(&node_id);
+    ui.horizontal(|ui| {
+        ui.add_space(row.depth as f32 * ui.spacing().indent);
+        let res = ui.selectable_label(selected, text);
+        if res.clicked() {
+            *clicked = Some(node_id);
+        }
+        if display_none {
+            res.on_hover_text("display: None. This node and its children are not laid out");
+        }
+        // only the visible rows are linted, as linting the whole tree is slow for large trees
+        let warnings = options.lint_cache.lint(tree, node_id);
+        if !warnings.is_empty() {
+            ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                .on_hover_text(lint_warnings_text(warnings));
+        }
+    });
 }
-fn taffy_style_editor(ui: &mut egui::Ui, tree: &mut TaffyTree, node_id: taffy::NodeId) {
+/// What [`taffy_style_editor`] needs besides the tree and the nodes.
+struct StyleEditorState<'a> {
+    /// The edits are recorded as overrides of the class of the node.
+    style_classes: &'a mut StyleClasses,
+    /// The edits go to the active variant of the node.
+    style_variants: &'a mut StyleVariants,
+    filter: &'a mut StyleFilter,
+    default_style: &'a Style,
+    lint_cache: &'a mut LintCache,
+}
+/// Edits the style of `node_id`. The edited fields are also applied to `other_nodes`.
+/// The fields that differ between the selected nodes are marked as mixed, and show the value of `node_id`.
+fn taffy_style_editor(
+    ui: &mut egui::Ui,
+    tree: &mut TaffyTree,
+    node_id: taffy::NodeId,
+    other_nodes: &[NodeId],
+    state: StyleEditorState<'_>,
+) {
+    let StyleEditorState {
+        style_classes,
+        style_variants,
+        filter,
+        default_style,
+        lint_cache,
+    } = state;
     let Ok(mut style) = tree.style(node_id).cloned() else {
         return;
     };
-    egui::Grid::new("style editor")
-        .num_columns(2)
-        .striped(true)
-        .show(ui, |ui| {
-            {
-                ui.label("display");
-
-                let mut selected = match style.display {
-                    taffy::Display::Block => 0,
-                    taffy::Display::Flex => 1,
-                    taffy::Display::Grid => 2,
-                    taffy::Display::None => 3,
-                };
-                ComboBox::from_id_salt("display").show_index(ui, &mut selected, 4, |i| match i {
-                    0 => "Block",
-                    1 => "Flex",
-                    2 => "Grid",
-                    3 => "None",
-                    _ => unreachable!(),
-                });
-                style.display = match selected {
-                    0 => taffy::Display::Block,
-                    1 => taffy::Display::Flex,
-                    2 => taffy::Display::Grid,
-                    3 => taffy::Display::None,
-                    _ => unreachable!(),
-                };
-                ui.end_row();
-            }
-            {
-                ui.label("box sizing");
-                let mut selected = match style.box_sizing {
-                    taffy::BoxSizing::ContentBox => 0,
-                    taffy::BoxSizing::BorderBox => 1,
-                };
-                ComboBox::from_id_salt("box sizing").show_index(
-                    ui,
-                    &mut selected,
-                    2,
-                    |i| match i {
-                        0 => "ContentBox",
-                        1 => "BorderBox",
-                        _ => unreachable!(),
-                    },
-                );
-                style.box_sizing = match selected {
-                    0 => taffy::BoxSizing::ContentBox,
-                    1 => taffy::BoxSizing::BorderBox,
-                    _ => unreachable!(),
-                };
-                ui.end_row();
-            }
-            {
-                ui.label("overflow");
-                ui.group(|ui| {
-                    ui.vertical(|ui| {
-                        for (salt, value) in [
-                            ("overflow_x", &mut style.overflow.x),
-                            ("overflow_y", &mut style.overflow.y),
-                        ] {
-                            let mut selected = match *value {
-                                taffy::Overflow::Visible => 0,
-                                taffy::Overflow::Hidden => 1,
-                                taffy::Overflow::Scroll => 2,
-                                taffy::Overflow::Clip => 3,
-                            };
-                            ComboBox::from_id_salt(salt).show_index(ui, &mut selected, 4, |i| {
-                                match i {
-                                    0 => "Visible",
-                                    1 => "Hidden",
-                                    2 => "Scroll",
-                                    3 => "Clip",
-                                    _ => unreachable!(),
-                                }
-                            });
-                            *value = match selected {
-                                0 => taffy::Overflow::Visible,
-                                1 => taffy::Overflow::Hidden,
-                                2 => taffy::Overflow::Scroll,
-                                3 => taffy::Overflow::Clip,
-                                _ => unreachable!(),
-                            };
-                        }
-                    });
-                });
-                ui.end_row();
-            }
-            {
-                ui.label("scrollbar width");
-                ui.add(DragValue::new(&mut style.scrollbar_width));
-                ui.end_row();
-            }
-            {
-                ui.label("position");
-                let mut selected = match style.position {
-                    taffy::Position::Relative => 0,
-                    taffy::Position::Absolute => 1,
-                };
-                ComboBox::from_id_salt("position").show_index(ui, &mut selected, 2, |i| match i {
-                    0 => "Relative",
-                    1 => "Absolute",
-                    _ => unreachable!(),
-                });
-                style.position = match selected {
-                    0 => taffy::Position::Relative,
-                    1 => taffy::Position::Absolute,
-                    _ => unreachable!(),
-                };
-                ui.end_row();
-            }
-            {
-                ui.label("inset");
-                ui.push_id("inset", |ui| {
-                    rect_len_percent_auto_ui(ui, &mut style.inset);
-                });
-                ui.end_row();
+    let old_style = style.clone();
+    let parent_display = tree
+        .parent(node_id)
+        .and_then(|parent| tree.style(parent).ok())
+        .map(|parent_style| parent_style.display);
+    let applies = |field: StyleField| field.applies_to(old_style.display, parent_display);
+    filter.ui(ui);
+    let baseline = if filter.compare_to_default_style {
+        default_style
+    } else {
+        &Style::DEFAULT
+    };
+    let modified = |field: StyleField| !field.is_equal(&old_style, baseline);
+    if !filter.show_all {
+        let hidden = StyleField::ALL
+            .iter()
+            .filter(|&&field| !applies(field))
+            .count();
+        if hidden != 0 {
+            let parent = match parent_display {
+                Some(display) => format!("{display:?}"),
+                None => "none (root)".to_string(),
+            };
+            ui.weak(format!(
+                "{hidden} properties are hidden, as they don't apply to display: {:?} with parent display: {parent}",
+                old_style.display
+            ));
+        }
+    }
+    let mixed_fields: Vec<StyleField> = StyleField::ALL
+        .iter()
+        .copied()
+        .filter(|field| {
+            other_nodes.iter().any(|&other| {
+                tree.style(other)
+                    .is_ok_and(|other_style| !field.is_equal(&style, other_style))
+            })
+        })
+        .collect();
+    if !other_nodes.is_empty() {
+        ui.label(format!(
+            "editing {} nodes ({} fields with mixed values)",
+            other_nodes.len() + 1,
+            mixed_fields.len()
+        ));
+    }
+    if let Some(membership) = style_classes.members.get(&node_id) {
+        ui.label(format!(
+            "class: {} ({} overridden fields)",
+            membership.class,
+            membership.overrides.len()
+        ));
+    }
+    let lints = lint_cache.lint(tree, node_id);
+    let mut inherit_field = None;
+    let mut reset_field = None;
+    let visible = |field: StyleField| filter.is_visible(field, applies(field), modified(field));
+    style_editor_grid(ui, "style editor", &mut style, visible, |ui, field| {
+        ui.horizontal(|ui| {
+            if modified(field) {
+                ui.label(egui::RichText::new(field.name()).strong());
+                if ui
+                    .small_button("⟲")
+                    .on_hover_text(format!(
+                        "differs from {}. click to reset this field",
+                        filter.baseline_name()
+                    ))
+                    .clicked()
+                {
+                    reset_field = Some(field);
+                }
+            } else {
+                ui.label(field.name());
             }
-            {
-                ui.label("size");
-                ui.push_id("size", |ui| {
-                    size_dimension_ui(ui, &mut style.size);
-                });
-                ui.end_row();
+            field_help_ui(ui, field);
+            let field_lints: Vec<LintWarning> = lints
+                .iter()
+                .filter(|warning| warning.field == field)
+                .cloned()
+                .collect();
+            if !field_lints.is_empty() {
+                ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                    .on_hover_text(lint_warnings_text(&field_lints));
             }
-            {
-                ui.label("min_size");
-                ui.push_id("min_size", |ui| {
-                    size_dimension_ui(ui, &mut style.min_size);
-                });
-                ui.end_row();
+            if mixed_fields.contains(&field) {
+                ui.weak("≠").on_hover_text(
+                    "mixed: the selected nodes have different values. editing sets the value on all of them",
+                );
             }
-            {
-                ui.label("max_size");
-                ui.push_id("max_size", |ui| {
-                    size_dimension_ui(ui, &mut style.max_size);
-                });
-                ui.end_row();
+            match style_classes.is_overridden(node_id, field) {
+                Some(true) => {
+                    if ui
+                        .small_button("✏")
+                        .on_hover_text(
+                            "overridden locally. click to inherit the value from the class",
+                        )
+                        .clicked()
+                    {
+                        inherit_field = Some(field);
+                    }
+                }
+                Some(false) => {
+                    ui.weak("⬇").on_hover_text("inherited from the class");
+                }
+                None => {}
             }
-            {
-                ui.label("aspect_ratio");
+        });
+    });
+    if let Some(field) = reset_field {
+        field.copy(baseline, &mut style);
+    }
+    style_classes.record_edit(node_id, &old_style, &style);
+    style_variants.record_edit(node_id, &old_style, &style);
+    for &other in other_nodes {
+        let Ok(mut other_style) = tree.style(other).cloned() else {
+            continue;
+        };
+        let old_other_style = other_style.clone();
+        for &field in StyleField::ALL {
+            field.copy_edit(&old_style, &style, &mut other_style);
+        }
+        if let Some(field) = reset_field {
+            field.copy(baseline, &mut other_style);
+        }
+        style_classes.record_edit(other, &old_other_style, &other_style);
+        style_variants.record_edit(other, &old_other_style, &other_style);
+        if let Some(field) = inherit_field {
+            style_classes.inherit_field(other, field, &mut other_style);
+        }
+        set_style_if_changed(tree, other, other_style);
+    }
+    if let Some(field) = inherit_field {
+        style_classes.inherit_field(node_id, field, &mut style);
+    }
+    set_style_if_changed(tree, node_id, style);
+}
+/// Create, apply, rename and edit the [`StyleClasses`].
+fn style_classes_ui(
+    ui: &mut egui::Ui,
+    tree: &mut TaffyTree,
+    current_node: NodeId,
+    style_classes: &mut StyleClasses,
+    selected_class: &mut Option<String>,
+    new_class_name: &mut String,
+) {
+    ui.horizontal(|ui| {
+        ui.add(egui::TextEdit::singleline(new_class_name).hint_text("class name"));
+        let name_available = !new_class_name.is_empty()
+            && !style_classes.classes.contains_key(new_class_name.as_str());
+        if ui
+            .add_enabled(
+                name_available,
+                egui::Button::new("new class from selected node"),
+            )
+            .on_hover_text(
+                "creates a class with the style of the selected node, and applies it to the node",
+            )
+            .clicked()
+        {
+            let style = tree.style(current_node).cloned().unwrap_or_default();
+            style_classes.classes.insert(new_class_name.clone(), style);
+            style_classes.apply(tree, current_node, new_class_name);
+            *selected_class = Some(std::mem::take(new_class_name));
+        }
+    });
+    ui.separator();
+    for class in style_classes.classes.keys() {
+        let count = style_classes.member_count(class);
+        if ui
+            .selectable_label(
+                selected_class.as_deref() == Some(class.as_str()),
+                format!("{class} ({count} nodes)"),
+            )
+            .clicked()
+        {
+            *selected_class = Some(class.clone());
+        }
+    }
+    let Some(class) = selected_class.clone() else {
+        return;
+    };
+    let Some(mut class_style) = style_classes.classes.get(&class).cloned() else {
+        *selected_class = None;
+        return;
+    };
+    ui.separator();
+    ui.horizontal_wrapped(|ui| {
+        if ui.button("apply to selected node").clicked() {
+            style_classes.apply(tree, current_node, &class);
+        }
+        let is_member = style_classes
+            .members
+            .get(&current_node)
+            .is_some_and(|membership| membership.class == class);
+        if ui
+            .add_enabled(is_member, egui::Button::new("detach selected node"))
+            .on_hover_text("the node keeps its current style, but stops following the class")
+            .clicked()
+        {
+            style_classes.detach(current_node);
+        }
+        let name_available = !new_class_name.is_empty()
+            && !style_classes.classes.contains_key(new_class_name.as_str());
+        if ui
+            .add_enabled(name_available, egui::Button::new("rename"))
+            .on_hover_text("renames the class to the name in the text field above")
+            .clicked()
+            && style_classes.rename(&class, new_class_name)
+        {
+            *selected_class = Some(std::mem::take(new_class_name));
+        }
+        if ui.button("delete class").clicked() {
+            style_classes.remove(&class);
+            *selected_class = None;
+        }
+    });
+    if selected_class.as_deref() != Some(class.as_str()) {
+        return;
+    }
+    ui.separator();
+    ui.push_id(&class, |ui| {
+        style_editor_grid(
+            ui,
+            "class style editor",
+            &mut class_style,
+            |_| true,
+            |ui, field| {
                 ui.horizontal(|ui| {
-                    ui.push_id("aspect_ratio", |ui| {
-                        let mut enabled = style.aspect_ratio.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.aspect_ratio = None;
-                            } else {
-                                style.aspect_ratio = Some(1.0);
-                            }
-                        }
-                        if let Some(ratio) = style.aspect_ratio.as_mut() {
-                            ui.add(DragValue::new(ratio));
-                        }
-                    });
-                });
-                ui.end_row();
-            }
-            {
-                ui.label("margin");
-                ui.push_id("margin", |ui| {
-                    rect_len_percent_auto_ui(ui, &mut style.margin)
+                    ui.label(field.name());
+                    field_help_ui(ui, field);
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("padding");
-                ui.push_id("padding", |ui| rect_len_percent_ui(ui, &mut style.padding));
-                ui.end_row();
-            }
-            {
-                ui.label("border");
-                ui.push_id("border", |ui| {
-                    rect_len_percent_ui(ui, &mut style.border);
+            },
+        );
+    });
+    if style_classes.classes.get(&class) != Some(&class_style) {
+        style_classes.classes.insert(class.clone(), class_style);
+        style_classes.propagate(tree, &class);
+    }
+}
+/// Shows the widget to edit a single field of the style. The caller takes care of the label and ending the grid row.
+fn style_field_ui(ui: &mut egui::Ui, field: StyleField, style: &mut Style) {
+    match field {
+        StyleField::Display => {
+            let mut selected = match style.display {
+                taffy::Display::Block => 0,
+                taffy::Display::Flex => 1,
+                taffy::Display::Grid => 2,
+                taffy::Display::None => 3,
+            };
+            ComboBox::from_id_salt("display").show_index(ui, &mut selected, 4, |i| match i {
+                0 => "Block",
+                1 => "Flex",
+                2 => "Grid",
+                3 => "None",
+                _ => unreachable!(),
+            });
+            style.display = match selected {
+                0 => taffy::Display::Block,
+                1 => taffy::Display::Flex,
+                2 => taffy::Display::Grid,
+                3 => taffy::Display::None,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::BoxSizing => {
+            let mut selected = match style.box_sizing {
+                taffy::BoxSizing::ContentBox => 0,
+                taffy::BoxSizing::BorderBox => 1,
+            };
+            ComboBox::from_id_salt("box sizing").show_index(ui, &mut selected, 2, |i| match i {
+                0 => "ContentBox",
+                1 => "BorderBox",
+                _ => unreachable!(),
+            });
+            style.box_sizing = match selected {
+                0 => taffy::BoxSizing::ContentBox,
+                1 => taffy::BoxSizing::BorderBox,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::Overflow => {
+            ui.group(|ui| {
+                ui.vertical(|ui| {
+                    for (salt, value) in [
+                        ("overflow_x", &mut style.overflow.x),
+                        ("overflow_y", &mut style.overflow.y),
+                    ] {
+                        let mut selected = match *value {
+                            taffy::Overflow::Visible => 0,
+                            taffy::Overflow::Hidden => 1,
+                            taffy::Overflow::Scroll => 2,
+                            taffy::Overflow::Clip => 3,
+                        };
+                        ComboBox::from_id_salt(salt).show_index(
+                            ui,
+                            &mut selected,
+                            4,
+                            |i| match i {
+                                0 => "Visible",
+                                1 => "Hidden",
+                                2 => "Scroll",
+                                3 => "Clip",
+                                _ => unreachable!(),
+                            },
+                        );
+                        *value = match selected {
+                            0 => taffy::Overflow::Visible,
+                            1 => taffy::Overflow::Hidden,
+                            2 => taffy::Overflow::Scroll,
+                            3 => taffy::Overflow::Clip,
+                            _ => unreachable!(),
+                        };
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("align_items");
-                ui.horizontal(|ui| {
-                    ui.push_id("align_items", |ui| {
-                        let mut enabled = style.align_items.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.align_items = None;
-                            } else {
-                                style.align_items = Some(AlignItems::Center);
-                            }
-                        }
-                        if let Some(align_items) = style.align_items.as_mut() {
-                            align_items_ui(ui, align_items);
+            });
+        }
+        StyleField::ScrollbarWidth => {
+            ui.add(DragValue::new(&mut style.scrollbar_width));
+        }
+        StyleField::Position => {
+            let mut selected = match style.position {
+                taffy::Position::Relative => 0,
+                taffy::Position::Absolute => 1,
+            };
+            ComboBox::from_id_salt("position").show_index(ui, &mut selected, 2, |i| match i {
+                0 => "Relative",
+                1 => "Absolute",
+                _ => unreachable!(),
+            });
+            style.position = match selected {
+                0 => taffy::Position::Relative,
+                1 => taffy::Position::Absolute,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::Inset => {
+            ui.push_id("inset", |ui| {
+                rect_len_percent_auto_ui(ui, &mut style.inset);
+            });
+        }
+        StyleField::Size => {
+            ui.push_id("size", |ui| {
+                size_dimension_ui(ui, &mut style.size);
+            });
+        }
+        StyleField::MinSize => {
+            ui.push_id("min_size", |ui| {
+                size_dimension_ui(ui, &mut style.min_size);
+            });
+        }
+        StyleField::MaxSize => {
+            ui.push_id("max_size", |ui| {
+                size_dimension_ui(ui, &mut style.max_size);
+            });
+        }
+        StyleField::AspectRatio => {
+            ui.horizontal(|ui| {
+                ui.push_id("aspect_ratio", |ui| {
+                    let mut enabled = style.aspect_ratio.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.aspect_ratio = None;
+                        } else {
+                            style.aspect_ratio = Some(1.0);
                         }
-                    });
+                    }
+                    if let Some(ratio) = style.aspect_ratio.as_mut() {
+                        ui.add(DragValue::new(ratio));
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("align_self");
-                ui.horizontal(|ui| {
-                    ui.push_id("align_self", |ui| {
-                        let mut enabled = style.align_self.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.align_self = None;
-                            } else {
-                                style.align_self = Some(AlignSelf::Center);
-                            }
-                        }
-                        if let Some(align_self) = style.align_self.as_mut() {
-                            align_items_ui(ui, align_self);
+            });
+        }
+        StyleField::Margin => {
+            ui.push_id("margin", |ui| {
+                rect_len_percent_auto_ui(ui, &mut style.margin)
+            });
+        }
+        StyleField::Padding => {
+            ui.push_id("padding", |ui| rect_len_percent_ui(ui, &mut style.padding));
+        }
+        StyleField::Border => {
+            ui.push_id("border", |ui| {
+                rect_len_percent_ui(ui, &mut style.border);
+            });
+        }
+        StyleField::AlignItems => {
+            ui.horizontal(|ui| {
+                ui.push_id("align_items", |ui| {
+                    let mut enabled = style.align_items.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.align_items = None;
+                        } else {
+                            style.align_items = Some(AlignItems::Center);
                         }
-                    });
+                    }
+                    if let Some(align_items) = style.align_items.as_mut() {
+                        align_items_ui(ui, align_items);
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("justify_items");
-                ui.horizontal(|ui| {
-                    ui.push_id("justify_items", |ui| {
-                        let mut enabled = style.justify_items.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.justify_items = None;
-                            } else {
-                                style.justify_items = Some(AlignItems::Center);
-                            }
-                        }
-                        if let Some(justify_items) = style.justify_items.as_mut() {
-                            align_items_ui(ui, justify_items);
+            });
+        }
+        StyleField::AlignSelf => {
+            ui.horizontal(|ui| {
+                ui.push_id("align_self", |ui| {
+                    let mut enabled = style.align_self.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.align_self = None;
+                        } else {
+                            style.align_self = Some(AlignSelf::Center);
                         }
-                    });
+                    }
+                    if let Some(align_self) = style.align_self.as_mut() {
+                        align_items_ui(ui, align_self);
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("justify_self");
-                ui.horizontal(|ui| {
-                    ui.push_id("justify_self", |ui| {
-                        let mut enabled = style.justify_self.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.justify_self = None;
-                            } else {
-                                style.justify_self = Some(AlignSelf::Center);
-                            }
-                        }
-                        if let Some(justify_self) = style.justify_self.as_mut() {
-                            align_items_ui(ui, justify_self);
+            });
+        }
+        StyleField::JustifyItems => {
+            ui.horizontal(|ui| {
+                ui.push_id("justify_items", |ui| {
+                    let mut enabled = style.justify_items.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.justify_items = None;
+                        } else {
+                            style.justify_items = Some(AlignItems::Center);
                         }
-                    });
+                    }
+                    if let Some(justify_items) = style.justify_items.as_mut() {
+                        align_items_ui(ui, justify_items);
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("align_content");
-                ui.horizontal(|ui| {
-                    ui.push_id("align_content", |ui| {
-                        let mut enabled = style.align_content.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.align_content = None;
-                            } else {
-                                style.align_content = Some(AlignContent::Center);
-                            }
-                        }
-                        if let Some(align_content) = style.align_content.as_mut() {
-                            align_content_ui(ui, align_content);
+            });
+        }
+        StyleField::JustifySelf => {
+            ui.horizontal(|ui| {
+                ui.push_id("justify_self", |ui| {
+                    let mut enabled = style.justify_self.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.justify_self = None;
+                        } else {
+                            style.justify_self = Some(AlignSelf::Center);
                         }
-                    });
+                    }
+                    if let Some(justify_self) = style.justify_self.as_mut() {
+                        align_items_ui(ui, justify_self);
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("justify_content");
-                ui.horizontal(|ui| {
-                    ui.push_id("justify_content", |ui| {
-                        let mut enabled = style.justify_content.is_some();
-                        if ui.checkbox(&mut enabled, "enabled").changed() {
-                            if !enabled {
-                                style.justify_content = None;
-                            } else {
-                                style.justify_content = Some(AlignContent::Center);
-                            }
+            });
+        }
+        StyleField::AlignContent => {
+            ui.horizontal(|ui| {
+                ui.push_id("align_content", |ui| {
+                    let mut enabled = style.align_content.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.align_content = None;
+                        } else {
+                            style.align_content = Some(AlignContent::Center);
                         }
-                        if let Some(justify_content) = style.justify_content.as_mut() {
-                            align_content_ui(ui, justify_content);
+                    }
+                    if let Some(align_content) = style.align_content.as_mut() {
+                        align_content_ui(ui, align_content);
+                    }
+                });
+            });
+        }
+        StyleField::JustifyContent => {
+            ui.horizontal(|ui| {
+                ui.push_id("justify_content", |ui| {
+                    let mut enabled = style.justify_content.is_some();
+                    if ui.checkbox(&mut enabled, "enabled").changed() {
+                        if !enabled {
+                            style.justify_content = None;
+                        } else {
+                            style.justify_content = Some(AlignContent::Center);
                         }
-                    });
+                    }
+                    if let Some(justify_content) = style.justify_content.as_mut() {
+                        align_content_ui(ui, justify_content);
+                    }
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("gap");
-                ui.vertical(|ui| {
-                    ui.push_id("gap_width", |ui| {
-                        len_percent_ui(ui, &mut style.gap.width);
-                    });
-                    ui.push_id("gap_height", |ui| {
-                        len_percent_ui(ui, &mut style.gap.height);
-                    });
+            });
+        }
+        StyleField::Gap => {
+            ui.vertical(|ui| {
+                ui.push_id("gap_width", |ui| {
+                    len_percent_ui(ui, &mut style.gap.width);
                 });
-                ui.end_row();
-            }
-            {
-                ui.label("text_align");
-                let mut selected = match style.text_align {
-                    TextAlign::Auto => 0,
-                    TextAlign::LegacyLeft => 1,
-                    TextAlign::LegacyRight => 2,
-                    TextAlign::LegacyCenter => 3,
-                };
-                ComboBox::from_id_salt("text_align").show_index(
-                    ui,
-                    &mut selected,
-                    4,
-                    |i| match i {
-                        0 => "Auto",
-                        1 => "Left",
-                        2 => "Right",
-                        3 => "Center",
-                        _ => unreachable!(),
-                    },
-                );
-                style.text_align = match selected {
-                    0 => TextAlign::Auto,
-                    1 => TextAlign::LegacyLeft,
-                    2 => TextAlign::LegacyRight,
-                    3 => TextAlign::LegacyCenter,
-                    _ => unreachable!(),
-                };
-                ui.end_row();
-            }
-            {
-                ui.label("flex_direction");
-                let mut selected = match style.flex_direction {
-                    FlexDirection::Row => 0,
-                    FlexDirection::Column => 1,
-                    FlexDirection::RowReverse => 2,
-                    FlexDirection::ColumnReverse => 3,
-                };
-                ComboBox::from_id_salt("flex_direction").show_index(ui, &mut selected, 4, |i| {
-                    match i {
-                        0 => "Row",
-                        1 => "Column",
-                        2 => "RowReverse",
-                        3 => "ColumnReverse",
-                        _ => unreachable!(),
-                    }
+                ui.push_id("gap_height", |ui| {
+                    len_percent_ui(ui, &mut style.gap.height);
                 });
-                style.flex_direction = match selected {
-                    0 => FlexDirection::Row,
-                    1 => FlexDirection::Column,
-                    2 => FlexDirection::RowReverse,
-                    3 => FlexDirection::ColumnReverse,
-                    _ => unreachable!(),
-                };
-                ui.end_row();
-            }
-            {
-                ui.label("flex_wrap");
-
-                let mut selected = match style.flex_wrap {
-                    taffy::FlexWrap::NoWrap => 0,
-                    taffy::FlexWrap::Wrap => 1,
-                    taffy::FlexWrap::WrapReverse => 2,
-                };
-                ComboBox::from_id_salt("flex_wrap").show_index(ui, &mut selected, 3, |i| match i {
-                    0 => "NoWrap",
-                    1 => "Wrap",
-                    2 => "WrapReverse",
+            });
+        }
+        StyleField::TextAlign => {
+            let mut selected = match style.text_align {
+                TextAlign::Auto => 0,
+                TextAlign::LegacyLeft => 1,
+                TextAlign::LegacyRight => 2,
+                TextAlign::LegacyCenter => 3,
+            };
+            ComboBox::from_id_salt("text_align").show_index(ui, &mut selected, 4, |i| match i {
+                0 => "Auto",
+                1 => "Left",
+                2 => "Right",
+                3 => "Center",
+                _ => unreachable!(),
+            });
+            style.text_align = match selected {
+                0 => TextAlign::Auto,
+                1 => TextAlign::LegacyLeft,
+                2 => TextAlign::LegacyRight,
+                3 => TextAlign::LegacyCenter,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::FlexDirection => {
+            let mut selected = match style.flex_direction {
+                FlexDirection::Row => 0,
+                FlexDirection::Column => 1,
+                FlexDirection::RowReverse => 2,
+                FlexDirection::ColumnReverse => 3,
+            };
+            ComboBox::from_id_salt("flex_direction").show_index(
+                ui,
+                &mut selected,
+                4,
+                |i| match i {
+                    0 => "Row",
+                    1 => "Column",
+                    2 => "RowReverse",
+                    3 => "ColumnReverse",
                     _ => unreachable!(),
-                });
-                style.flex_wrap = match selected {
-                    0 => taffy::FlexWrap::NoWrap,
-                    1 => taffy::FlexWrap::Wrap,
-                    2 => taffy::FlexWrap::WrapReverse,
+                },
+            );
+            style.flex_direction = match selected {
+                0 => FlexDirection::Row,
+                1 => FlexDirection::Column,
+                2 => FlexDirection::RowReverse,
+                3 => FlexDirection::ColumnReverse,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::FlexWrap => {
+            let mut selected = match style.flex_wrap {
+                taffy::FlexWrap::NoWrap => 0,
+                taffy::FlexWrap::Wrap => 1,
+                taffy::FlexWrap::WrapReverse => 2,
+            };
+            ComboBox::from_id_salt("flex_wrap").show_index(ui, &mut selected, 3, |i| match i {
+                0 => "NoWrap",
+                1 => "Wrap",
+                2 => "WrapReverse",
+                _ => unreachable!(),
+            });
+            style.flex_wrap = match selected {
+                0 => taffy::FlexWrap::NoWrap,
+                1 => taffy::FlexWrap::Wrap,
+                2 => taffy::FlexWrap::WrapReverse,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::FlexBasis => {
+            ui.push_id("flex_basis", |ui| {
+                dimension_ui(ui, &mut style.flex_basis);
+            });
+        }
+        StyleField::FlexGrow => {
+            ui.add(DragValue::new(&mut style.flex_grow));
+        }
+        StyleField::FlexShrink => {
+            ui.add(DragValue::new(&mut style.flex_shrink));
+        }
+        StyleField::GridTemplateRows => {
+            ui.push_id("grid_template_rows", |ui| {
+                css_text_ui(ui, &mut style.grid_template_rows);
+            });
+        }
+        StyleField::GridTemplateColumns => {
+            ui.push_id("grid_template_columns", |ui| {
+                css_text_ui(ui, &mut style.grid_template_columns);
+            });
+        }
+        StyleField::GridAutoRows => {
+            ui.push_id("grid_auto_rows", |ui| {
+                css_text_ui(ui, &mut style.grid_auto_rows);
+            });
+        }
+        StyleField::GridAutoColumns => {
+            ui.push_id("grid_auto_columns", |ui| {
+                css_text_ui(ui, &mut style.grid_auto_columns);
+            });
+        }
+        StyleField::GridAutoFlow => {
+            let mut selected = match style.grid_auto_flow {
+                GridAutoFlow::Row => 0,
+                GridAutoFlow::Column => 1,
+                GridAutoFlow::RowDense => 2,
+                GridAutoFlow::ColumnDense => 3,
+            };
+            ComboBox::from_id_salt("grid_auto_flow").show_index(
+                ui,
+                &mut selected,
+                4,
+                |i| match i {
+                    0 => "Row",
+                    1 => "Column",
+                    2 => "RowDense",
+                    3 => "ColumnDense",
                     _ => unreachable!(),
-                };
-                ui.end_row();
-            }
-            {
-                ui.label("flex_basis");
-                ui.push_id("flex_basis", |ui| {
-                    dimension_ui(ui, &mut style.flex_basis);
-                });
-                ui.end_row();
-            }
-            {
-                ui.label("flex_grow");
-                ui.add(DragValue::new(&mut style.flex_grow));
-                ui.end_row();
-            }
-            {
-                ui.label("flex_shrink");
-                ui.add(DragValue::new(&mut style.flex_shrink));
-                ui.end_row();
+                },
+            );
+            style.grid_auto_flow = match selected {
+                0 => GridAutoFlow::Row,
+                1 => GridAutoFlow::Column,
+                2 => GridAutoFlow::RowDense,
+                3 => GridAutoFlow::ColumnDense,
+                _ => unreachable!(),
+            };
+        }
+        StyleField::GridRow => {
+            ui.push_id("grid_row", |ui| {
+                css_text_ui(ui, &mut style.grid_row);
+            });
+        }
+        StyleField::GridColumn => {
+            ui.push_id("grid_column", |ui| {
+                css_text_ui(ui, &mut style.grid_column);
+            });
+        }
+    }
+}
+/// Shows all the editable fields of `style` in a striped grid.
+/// `label_ui` draws the first column of every row, so that callers can add markers or buttons next to the field name.
+/// The fields are grouped into collapsible sections, and only the fields for which `visible` returns true are shown.
+fn style_editor_grid(
+    ui: &mut egui::Ui,
+    id_salt: &str,
+    style: &mut Style,
+    visible: impl Fn(StyleField) -> bool,
+    mut label_ui: impl FnMut(&mut egui::Ui, StyleField),
+) {
+    for &section in StyleSection::ALL {
+        let fields: Vec<StyleField> = StyleField::ALL
+            .iter()
+            .copied()
+            .filter(|&field| field.section() == section && visible(field))
+            .collect();
+        if fields.is_empty() {
+            continue;
+        }
+        egui::CollapsingHeader::new(section.name())
+            .id_salt((id_salt, section.name()))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new((id_salt, section.name()))
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for field in fields {
+                            label_ui(ui, field);
+                            style_field_ui(ui, field, style);
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+/// Decides which fields are shown in the style editor of the Node Editor.
+#[derive(Debug, Default)]
+struct StyleFilter {
+    /// Only the fields whose name contains this are shown.
+    search: String,
+    /// Show the fields that don't apply to the display mode of the node (or its parent) too.
+    show_all: bool,
+    /// Only show the fields that differ from the baseline.
+    only_modified: bool,
+    /// Compare the fields with the editor's default style instead of [`Style::DEFAULT`], to decide if they're modified.
+    compare_to_default_style: bool,
+}
+impl StyleFilter {
+    fn ui(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.search)
+                    .hint_text("🔍 search properties")
+                    .desired_width(150.0),
+            );
+            if !self.search.is_empty() && ui.small_button("✖").clicked() {
+                self.search.clear();
             }
+            ui.checkbox(&mut self.show_all, "show all").on_hover_text(
+                "also show the properties that have no effect with the display mode of the node or its parent",
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.only_modified, "only show modified");
+            ui.label("compared to");
+            let mut selected = usize::from(self.compare_to_default_style);
+            ComboBox::from_id_salt("modified baseline").show_index(
+                ui,
+                &mut selected,
+                2,
+                |i| match i {
+                    0 => "Style::DEFAULT",
+                    1 => "default style",
+                    _ => unreachable!(),
+                },
+            );
+            self.compare_to_default_style = selected == 1;
         });
-    tree.set_style(node_id, style).unwrap();
+    }
+    fn baseline_name(&self) -> &'static str {
+        if self.compare_to_default_style {
+            "the default style"
+        } else {
+            "Style::DEFAULT"
+        }
+    }
+    /// `applies` is whether the field has an effect on the node (see [`StyleField::applies_to`]),
+    /// and `modified` is whether it differs from the baseline.
+    fn is_visible(&self, field: StyleField, applies: bool, modified: bool) -> bool {
+        let search = self.search.trim().to_lowercase().replace(' ', "_");
+        let name = field.name().replace(' ', "_");
+        (self.show_all || applies) && (!self.only_modified || modified) && name.contains(&search)
+    }
 }
 #[allow(unused)]
 fn max_track_size_ui(ui: &mut egui::Ui, value: &mut taffy::MaxTrackSizingFunction) {
@@ -753,21 +2087,60 @@ fn align_items_ui(ui: &mut egui::Ui, value: &mut taffy::AlignItems) {
     };
 }
 fn rect_len_percent_ui(ui: &mut egui::Ui, value: &mut taffy::Rect<taffy::style::LengthPercentage>) {
-    ui.vertical(|ui| {
-        for (seed, value) in [
-            ("left", &mut value.left),
-            ("right", &mut value.right),
-            ("top", &mut value.top),
-            ("bottom", &mut value.bottom),
-        ] {
-            ui.push_id(seed, |ui| {
-                ui.horizontal(|ui| {
-                    ui.label(seed);
-                    len_percent_ui(ui, value);
+    rect_ui(ui, value, len_percent_ui);
+}
+/// A collapsible editor of the four sides of a rect.
+/// The header has the css shorthand (which doubles as a summary when collapsed) and a toggle to link all sides,
+/// so that editing one side sets all four of them.
+fn rect_ui<T: CssValue + Copy + PartialEq>(
+    ui: &mut egui::Ui,
+    value: &mut taffy::Rect<T>,
+    side_ui: fn(&mut egui::Ui, &mut T),
+) {
+    let id = ui.make_persistent_id("rect");
+    let link_id = id.with("link sides");
+    let mut linked = ui.data_mut(|data| *data.get_persisted_mut_or_default::<bool>(link_id));
+    egui::collapsing_header::CollapsingState::load_with_default_open(ui.ctx(), id, false)
+        .show_header(ui, |ui| {
+            css_text_ui(ui, value);
+            if ui
+                .toggle_value(&mut linked, "🔗")
+                .on_hover_text("link all sides, so that editing one side changes all of them")
+                .changed()
+            {
+                ui.data_mut(|data| data.insert_persisted(link_id, linked));
+            }
+        })
+        .body(|ui| {
+            let old = *value;
+            for (seed, value) in [
+                ("left", &mut value.left),
+                ("right", &mut value.right),
+                ("top", &mut value.top),
+                ("bottom", &mut value.bottom),
+            ] {
+                ui.push_id(seed, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(seed);
+                        side_ui(ui, value);
+                    });
                 });
-            });
-        }
-    });
+            }
+            if linked {
+                let changed = [value.left, value.right, value.top, value.bottom]
+                    .into_iter()
+                    .zip([old.left, old.right, old.top, old.bottom])
+                    .find(|(new, old)| new != old);
+                if let Some((new, _)) = changed {
+                    *value = taffy::Rect {
+                        left: new,
+                        right: new,
+                        top: new,
+                        bottom: new,
+                    };
+                }
+            }
+        });
 }
 fn len_percent_ui(ui: &mut egui::Ui, value: &mut LengthPercentage) {
     let mut inner;
@@ -794,6 +2167,7 @@ fn len_percent_ui(ui: &mut egui::Ui, value: &mut LengthPercentage) {
         } else {
             *value = LengthPercentage::Percent(inner);
         }
+        css_text_ui(ui, value);
     });
 }
 fn size_dimension_ui(ui: &mut egui::Ui, value: &mut taffy::Size<taffy::Dimension>) {
@@ -831,27 +2205,14 @@ fn dimension_ui(ui: &mut egui::Ui, value: &mut taffy::Dimension) {
             2 => Dimension::Auto,
             _ => unreachable!(),
         };
+        css_text_ui(ui, value);
     });
 }
 fn rect_len_percent_auto_ui(
     ui: &mut egui::Ui,
     value: &mut taffy::Rect<taffy::style::LengthPercentageAuto>,
 ) {
-    ui.vertical(|ui| {
-        for (seed, value) in [
-            ("left", &mut value.left),
-            ("right", &mut value.right),
-            ("top", &mut value.top),
-            ("bottom", &mut value.bottom),
-        ] {
-            ui.horizontal(|ui| {
-                ui.push_id(seed, |ui| {
-                    ui.label(seed);
-                    len_percent_auto_ui(ui, value);
-                });
-            });
-        }
-    });
+    rect_ui(ui, value, len_percent_auto_ui);
 }
 fn len_percent_auto_ui(ui: &mut egui::Ui, value: &mut taffy::style::LengthPercentageAuto) {
     let mut inner = 0.0;
@@ -889,6 +2250,7 @@ fn len_percent_auto_ui(ui: &mut egui::Ui, value: &mut taffy::style::LengthPercen
             2 => *value = taffy::LengthPercentageAuto::Auto,
             _ => unreachable!(),
         }
+        css_text_ui(ui, value);
     });
 }
 
@@ -902,29 +2264,38 @@ enum NodeEvent {
 /// If it is consumed, it will return the node id of self.
 /// If it is consumed by one of the children, then it will return the returned node id.
 /// If it is not consumed by any of the children or itself, it will return None.
+///
+/// `clip` is the area in which the node is visible (based on the overflow of ancestors). Events outside of it are ignored.
 fn node_event_recursive(
-    tree: &mut TaffyTree,
+    tree: &TaffyTree,
     ev: NodeEvent,
     offset: Vec2,
     node_id: taffy::NodeId,
+    scroll_offsets: &HashMap<NodeId, Vec2>,
+    clip: egui::Rect,
 ) -> Option<NodeId> {
     let layout = *tree.get_final_layout(node_id);
 
-    let new_offset = offset + egui::vec2(layout.location.x, layout.location.y);
+    let node_rect = egui::Rect::from_min_size(
+        [layout.location.x, layout.location.y].into(),
+        [layout.size.width, layout.size.height].into(),
+    )
+    .translate(offset);
+    let new_offset = offset + egui::vec2(layout.location.x, layout.location.y)
+        - scroll_offset(tree, node_id, scroll_offsets);
+    let children_clip = overflow_clip_rect(tree, node_id, node_rect, clip);
 
     let mut children = tree.children(node_id).unwrap_or_default();
     children.sort_unstable_by_key(|i| tree.get_final_layout(*i).order);
     for child in children {
-        if let Some(new_node_id) = node_event_recursive(tree, ev, new_offset, child) {
+        if let Some(new_node_id) =
+            node_event_recursive(tree, ev, new_offset, child, scroll_offsets, children_clip)
+        {
             return Some(new_node_id);
         }
     }
 
-    let node_rect = egui::Rect::from_min_size(
-        [layout.location.x, layout.location.y].into(),
-        [layout.size.width, layout.size.height].into(),
-    )
-    .translate(offset);
+    let node_rect = node_rect.intersect(clip);
     match ev {
         NodeEvent::Hover(vec2) => {
             if node_rect.contains(egui::pos2(vec2.x, vec2.y)) {
@@ -939,12 +2310,133 @@ fn node_event_recursive(
     }
     None
 }
+/// Scrolls the nearest scroll container (starting from `node_id` and going up the ancestors) by `delta`.
+/// Returns false if there's no scroll container to scroll.
+fn scroll_recursive(
+    tree: &TaffyTree,
+    node_id: taffy::NodeId,
+    delta: Vec2,
+    scroll_offsets: &mut HashMap<NodeId, Vec2>,
+) -> bool {
+    let Ok(style) = tree.style(node_id) else {
+        return false;
+    };
+    let scroll_x = style.overflow.x == Overflow::Scroll && delta.x != 0.0;
+    let scroll_y = style.overflow.y == Overflow::Scroll && delta.y != 0.0;
+    if !scroll_x && !scroll_y {
+        return tree
+            .parent(node_id)
+            .is_some_and(|parent| scroll_recursive(tree, parent, delta, scroll_offsets));
+    }
+    let mut offset = scroll_offset(tree, node_id, scroll_offsets);
+    if scroll_x {
+        offset.x -= delta.x;
+    }
+    if scroll_y {
+        offset.y -= delta.y;
+    }
+    scroll_offsets.insert(node_id, offset);
+    // clamp the value we just inserted
+    let offset = scroll_offset(tree, node_id, scroll_offsets);
+    scroll_offsets.insert(node_id, offset);
+    true
+}
+/// The maximum distance that the contents of the node can be scrolled by.
+/// The content size is measured from the border box origin, and the scrollport ends before the end border and the scrollbar.
+fn max_scroll_offset(layout: &taffy::Layout) -> Vec2 {
+    egui::vec2(
+        (layout.content_size.width - layout.size.width
+            + layout.border.right
+            + layout.scrollbar_size.width)
+            .max(0.0),
+        (layout.content_size.height - layout.size.height
+            + layout.border.bottom
+            + layout.scrollbar_size.height)
+            .max(0.0),
+    )
+}
+/// Gets the scroll offset of the node, clamped to the scrollable range.
+/// Only the axes with [`Overflow::Scroll`] can have a non-zero offset.
+fn scroll_offset(
+    tree: &TaffyTree,
+    node_id: taffy::NodeId,
+    scroll_offsets: &HashMap<NodeId, Vec2>,
+) -> Vec2 {
+    let Some(offset) = scroll_offsets.get(&node_id).copied() else {
+        return Vec2::ZERO;
+    };
+    let Ok(style) = tree.style(node_id) else {
+        return Vec2::ZERO;
+    };
+    let max = max_scroll_offset(tree.get_final_layout(node_id));
+    egui::vec2(
+        if style.overflow.x == Overflow::Scroll {
+            offset.x.clamp(0.0, max.x)
+        } else {
+            0.0
+        },
+        if style.overflow.y == Overflow::Scroll {
+            offset.y.clamp(0.0, max.y)
+        } else {
+            0.0
+        },
+    )
+}
+/// The rect that the children of the node are clipped to.
+/// On the axes where overflow is not [`Overflow::Visible`], children are clipped to the padding box (excluding the scrollbar gutter).
+/// On the other axes, we just use the `clip` of the node itself.
+fn overflow_clip_rect(
+    tree: &TaffyTree,
+    node_id: taffy::NodeId,
+    node_rect: egui::Rect,
+    clip: egui::Rect,
+) -> egui::Rect {
+    let Ok(style) = tree.style(node_id) else {
+        return clip;
+    };
+    let layout = tree.get_final_layout(node_id);
+    let padding_box = egui::Rect::from_min_max(
+        egui::pos2(
+            node_rect.min.x + layout.border.left,
+            node_rect.min.y + layout.border.top,
+        ),
+        egui::pos2(
+            node_rect.max.x - layout.border.right - layout.scrollbar_size.width,
+            node_rect.max.y - layout.border.bottom - layout.scrollbar_size.height,
+        ),
+    );
+    let x_range = if style.overflow.x == Overflow::Visible {
+        clip.x_range()
+    } else {
+        padding_box.x_range()
+    };
+    let y_range = if style.overflow.y == Overflow::Visible {
+        clip.y_range()
+    } else {
+        padding_box.y_range()
+    };
+    egui::Rect::from_x_y_ranges(x_range, y_range).intersect(clip)
+}
+/// Everything that decides how the nodes are painted, and doesn't change while recursing through the tree.
+struct PaintOptions<'a> {
+    focused_node: NodeId,
+    /// the other selected nodes, which get a thinner outline than the focused node.
+    selected_nodes: &'a [NodeId],
+    /// nodes with excessive re-measurement (from the Layout Cache window), outlined in orange.
+    hot_nodes: &'a [NodeId],
+    /// nodes whose edges rounding moved by more than half a pixel (from the Sub-pixel Overlay), outlined in magenta.
+    subpixel: &'a HashMap<NodeId, RoundingError>,
+    scroll_offsets: &'a HashMap<NodeId, Vec2>,
+    settings: &'a PaintSettings,
+    palette: Palette,
+}
 fn node_tree_paint_recursive(
     tree: &TaffyTree,
     node_id: taffy::NodeId,
     painter: &Painter,
     offset: Vec2,
-    focused_node: NodeId,
+    depth: usize,
+    options: &PaintOptions<'_>,
 ) {
     let layout = *tree.get_final_layout(node_id);
     let node_rect = egui::Rect::from_min_size(
@@ -952,6 +2444,14 @@ fn node_tree_paint_recursive(
         [layout.size.width, layout.size.height].into(),
     );
     let margin_rect = node_rect.translate(offset);
+    // skip the nodes (and their children) that are scrolled out of view
+    let subtree_rect = margin_rect.union(egui::Rect::from_min_size(
+        margin_rect.min,
+        [layout.content_size.width, layout.content_size.height].into(),
+    ));
+    if !painter.clip_rect().intersects(subtree_rect) {
+        return;
+    }
     /// Takes a rect, shrinks it by cutting the respective side with values from the cuts and gives us the sub rect
     fn get_sub_rect(rect: egui::Rect, cuts: taffy::Rect<f32>) -> egui::Rect {
         egui::Rect::from_min_max(
@@ -959,12 +2459,25 @@ fn node_tree_paint_recursive(
             egui::pos2(rect.max.x - cuts.right, rect.max.y - cuts.bottom),
         )
     }
-    painter.rect_filled(
-        margin_rect,
-        0.0,
-        Color32::from_hex("#ff7046").unwrap_or_default(),
-    );
-    if focused_node == node_id {
+    let colors = options
+        .settings
+        .area_colors(&options.palette, node_id, depth);
+    let paint_area = |rect: egui::Rect, color: Color32| {
+        if options.settings.fill_mode == FillMode::Wireframe {
+            painter.rect_stroke(rect, 0.0, Stroke::new(1.0_f32, color));
+        } else {
+            painter.rect_filled(rect, 0.0, color);
+        }
+    };
+    paint_area(margin_rect, colors.margin);
+    let outline_width: Option<f32> = if options.focused_node == node_id {
+        Some(5.0)
+    } else if options.selected_nodes.contains(&node_id) {
+        Some(2.0)
+    } else {
+        None
+    };
+    if let Some(outline_width) = outline_width {
         painter.add(egui::Shape::dashed_line(
             &[
                 margin_rect.left_top(),
@@ -973,43 +2486,389 @@ fn node_tree_paint_recursive(
                 margin_rect.left_bottom(),
                 margin_rect.left_top(),
             ],
-            Stroke::new(5.0, Color32::DEBUG_COLOR),
+            Stroke::new(outline_width, Color32::DEBUG_COLOR),
             10.0,
             10.0,
         ));
         // painter.rect_stroke(margin_rect, 3.0, Stroke::new(5.0, Color32::RED));
     }
+    if options.hot_nodes.contains(&node_id) {
+        painter.rect_stroke(
+            margin_rect.shrink(1.5),
+            0.0,
+            Stroke::new(3.0_f32, Color32::ORANGE),
+        );
+    }
     let border_rect = get_sub_rect(margin_rect, layout.margin);
-    painter.rect_filled(
-        border_rect,
-        0.0,
-        Color32::from_hex("#00a6c3").unwrap_or_default(),
-    );
+    paint_area(border_rect, colors.border);
     let padding_rect = get_sub_rect(border_rect, layout.border);
-    painter.rect_filled(
-        padding_rect,
-        0.0,
-        Color32::from_hex("#fac357").unwrap_or_default(),
-    );
+    paint_area(padding_rect, colors.padding);
     let content_rect = get_sub_rect(padding_rect, layout.padding);
-    painter.rect_filled(
-        content_rect,
-        0.0,
-        Color32::from_hex("#00c4a8").unwrap_or_default(),
-    );
-    let new_offset = offset + egui::vec2(layout.location.x, layout.location.y);
+    paint_area(content_rect, colors.content);
+    let scroll = scroll_offset(tree, node_id, options.scroll_offsets);
+    let new_offset = offset + egui::vec2(layout.location.x, layout.location.y) - scroll;
     if tree.child_count(node_id) != 0 {
+        let children_painter = painter.with_clip_rect(overflow_clip_rect(
+            tree,
+            node_id,
+            margin_rect,
+            painter.clip_rect(),
+        ));
         let mut children = tree.children(node_id).unwrap_or_default();
         children.sort_unstable_by_key(|i| tree.get_final_layout(*i).order);
         for child in children {
-            node_tree_paint_recursive(tree, child, painter, new_offset, focused_node);
+            node_tree_paint_recursive(
+                tree,
+                child,
+                &children_painter,
+                new_offset,
+                depth + 1,
+                options,
+            );
+        }
+    }
+    // outline of the content that overflows the node
+    if layout.content_size.width > layout.size.width
+        || layout.content_size.height > layout.size.height
+    {
+        let content_size_rect = egui::Rect::from_min_size(
+            margin_rect.min - scroll,
+            [layout.content_size.width, layout.content_size.height].into(),
+        );
+        painter.add(egui::Shape::dashed_line(
+            &[
+                content_size_rect.left_top(),
+                content_size_rect.right_top(),
+                content_size_rect.right_bottom(),
+                content_size_rect.left_bottom(),
+                content_size_rect.left_top(),
+            ],
+            Stroke::new(1.0_f32, Color32::BLACK),
+            4.0,
+            4.0,
+        ));
+    }
+    scrollbar_paint(&layout, painter, margin_rect, scroll);
+    if let Some(error) = options.subpixel.get(&node_id) {
+        painter.rect_stroke(
+            margin_rect,
+            0.0,
+            Stroke::new(2.0_f32, Color32::from_rgb(255, 0, 255)),
+        );
+        // where the node would be without rounding
+        let unrounded_rect = egui::Rect::from_min_size(
+            margin_rect.min + egui::vec2(error.drift.x, error.drift.y),
+            egui::vec2(error.unrounded_size.width, error.unrounded_size.height),
+        );
+        painter.rect_stroke(unrounded_rect, 0.0, Stroke::new(1.0_f32, Color32::WHITE));
+    }
+}
+/// Paints the scrollbar gutters (of `scrollbar_size`) and a thumb that shows the scroll position.
+fn scrollbar_paint(layout: &taffy::Layout, painter: &Painter, node_rect: egui::Rect, scroll: Vec2) {
+    let inner = egui::Rect::from_min_max(
+        egui::pos2(
+            node_rect.min.x + layout.border.left,
+            node_rect.min.y + layout.border.top,
+        ),
+        egui::pos2(
+            node_rect.max.x - layout.border.right,
+            node_rect.max.y - layout.border.bottom,
+        ),
+    );
+    let max_scroll = max_scroll_offset(layout);
+    let gutter_color = Color32::from_gray(200);
+    let thumb_color = Color32::from_gray(120);
+    if layout.scrollbar_size.width > 0.0 {
+        let gutter = egui::Rect::from_min_max(
+            egui::pos2(inner.max.x - layout.scrollbar_size.width, inner.min.y),
+            egui::pos2(inner.max.x, inner.max.y - layout.scrollbar_size.height),
+        );
+        painter.rect_filled(gutter, 0.0, gutter_color);
+        let visible = gutter.height() / (gutter.height() + max_scroll.y);
+        let thumb_height = gutter.height() * visible;
+        let thumb_top = if max_scroll.y > 0.0 {
+            (gutter.height() - thumb_height) * scroll.y / max_scroll.y
+        } else {
+            0.0
+        };
+        painter.rect_filled(
+            egui::Rect::from_min_size(
+                gutter.min + egui::vec2(0.0, thumb_top),
+                egui::vec2(gutter.width(), thumb_height),
+            )
+            .shrink(1.0),
+            2.0,
+            thumb_color,
+        );
+    }
+    if layout.scrollbar_size.height > 0.0 {
+        let gutter = egui::Rect::from_min_max(
+            egui::pos2(inner.min.x, inner.max.y - layout.scrollbar_size.height),
+            egui::pos2(inner.max.x - layout.scrollbar_size.width, inner.max.y),
+        );
+        painter.rect_filled(gutter, 0.0, gutter_color);
+        let visible = gutter.width() / (gutter.width() + max_scroll.x);
+        let thumb_width = gutter.width() * visible;
+        let thumb_left = if max_scroll.x > 0.0 {
+            (gutter.width() - thumb_width) * scroll.x / max_scroll.x
+        } else {
+            0.0
+        };
+        painter.rect_filled(
+            egui::Rect::from_min_size(
+                gutter.min + egui::vec2(thumb_left, 0.0),
+                egui::vec2(thumb_width, gutter.height()),
+            )
+            .shrink(1.0),
+            2.0,
+            thumb_color,
+        );
+    }
+}
+/// Lays out a copy of the tree in which the [`taffy::Display::None`] nodes use the default display mode instead.
+/// As the node ids stay the same in the clone, we can use it to look up where the hidden nodes would be.
+fn ghost_tree(
+    tree: &TaffyTree,
+    root: NodeId,
+    available_space: Size<taffy::AvailableSpace>,
+) -> TaffyTree {
+    fn unhide_recursive(tree: &mut TaffyTree, node_id: NodeId) {
+        if let Ok(style) = tree.style(node_id) {
+            if style.display == taffy::Display::None {
+                let mut style = style.clone();
+                style.display = taffy::Display::DEFAULT;
+                tree.set_style(node_id, style).unwrap();
+            }
+        }
+        for child in tree.children(node_id).unwrap_or_default() {
+            unhide_recursive(tree, child);
+        }
+    }
+    let mut ghost = tree.clone();
+    unhide_recursive(&mut ghost, root);
+    ghost.compute_layout(root, available_space).unwrap();
+    ghost
+}
+/// Paints faint outlines of the hidden nodes using the layout of the [`ghost_tree`].
+/// `hidden` is true if any of the ancestors is [`taffy::Display::None`] in the actual `tree`.
+fn ghost_paint_recursive(
+    ghost: &TaffyTree,
+    tree: &TaffyTree,
+    node_id: taffy::NodeId,
+    painter: &Painter,
+    offset: Vec2,
+    hidden: bool,
+    scroll_offsets: &HashMap<NodeId, Vec2>,
+) {
+    let hidden = hidden
+        || tree
+            .style(node_id)
+            .is_ok_and(|style| style.display == taffy::Display::None);
+    let layout = ghost.get_final_layout(node_id);
+    if hidden {
+        let node_rect = egui::Rect::from_min_size(
+            [layout.location.x, layout.location.y].into(),
+            [layout.size.width, layout.size.height].into(),
+        )
+        .translate(offset);
+        let color = Color32::from_gray(128).gamma_multiply(0.6);
+        painter.rect(
+            node_rect,
+            0.0,
+            color.gamma_multiply(0.2),
+            Stroke::new(1.0_f32, color),
+        );
+    }
+    let new_offset = offset + egui::vec2(layout.location.x, layout.location.y)
+        - scroll_offset(ghost, node_id, scroll_offsets);
+    for child in ghost.children(node_id).unwrap_or_default() {
+        ghost_paint_recursive(
+            ghost,
+            tree,
+            child,
+            painter,
+            new_offset,
+            hidden,
+            scroll_offsets,
+        );
+    }
+}
+/// The offset that [`node_tree_paint_recursive`] uses when painting this node.
+/// ie. the sum of locations of all ancestors minus their scroll offsets.
+fn node_paint_offset(
+    tree: &TaffyTree,
+    node_id: taffy::NodeId,
+    root_offset: Vec2,
+    scroll_offsets: &HashMap<NodeId, Vec2>,
+) -> Vec2 {
+    let mut offset = root_offset;
+    let mut current = node_id;
+    while let Some(parent) = tree.parent(current) {
+        let location = tree.get_final_layout(parent).location;
+        offset += egui::vec2(location.x, location.y) - scroll_offset(tree, parent, scroll_offsets);
+        current = parent;
+    }
+    offset
+}
+/// If the node is [`taffy::Position::Absolute`], this paints its containing block and
+/// dimension lines from each non-auto inset edge to the node, labelled with the resolved value.
+/// Insets position the margin box, so the margin continues the line up to the node as a dashed segment.
+///
+/// The containing block is the padding box of the parent (border box - border - scrollbar gutter), which is what
+/// taffy resolves insets against. Grid items with explicit placement use their grid area instead, which we don't show.
+fn absolute_guides_paint(
+    tree: &TaffyTree,
+    node_id: taffy::NodeId,
+    painter: &Painter,
+    root_offset: Vec2,
+    scroll_offsets: &HashMap<NodeId, Vec2>,
+) {
+    let Ok(style) = tree.style(node_id) else {
+        return;
+    };
+    if style.position != taffy::Position::Absolute {
+        return;
+    }
+    let Some(parent) = tree.parent(node_id) else {
+        return;
+    };
+    let parent_layout = tree.get_final_layout(parent);
+    let layout = tree.get_final_layout(node_id);
+    let parent_offset = node_paint_offset(tree, parent, root_offset, scroll_offsets);
+    let parent_rect = egui::Rect::from_min_size(
+        [parent_layout.location.x, parent_layout.location.y].into(),
+        [parent_layout.size.width, parent_layout.size.height].into(),
+    )
+    .translate(parent_offset);
+    let containing_block = egui::Rect::from_min_max(
+        parent_rect.min + egui::vec2(parent_layout.border.left, parent_layout.border.top),
+        parent_rect.max
+            - egui::vec2(
+                parent_layout.border.right + parent_layout.scrollbar_size.width,
+                parent_layout.border.bottom + parent_layout.scrollbar_size.height,
+            ),
+    );
+    let node_rect = egui::Rect::from_min_size(
+        [layout.location.x, layout.location.y].into(),
+        [layout.size.width, layout.size.height].into(),
+    )
+    .translate(node_paint_offset(
+        tree,
+        node_id,
+        root_offset,
+        scroll_offsets,
+    ));
+
+    let color = Color32::from_rgb(200, 0, 200);
+    let stroke = Stroke::new(1.5_f32, color);
+    painter.add(egui::Shape::dashed_line(
+        &[
+            containing_block.left_top(),
+            containing_block.right_top(),
+            containing_block.right_bottom(),
+            containing_block.left_bottom(),
+            containing_block.left_top(),
+        ],
+        stroke,
+        6.0,
+        3.0,
+    ));
+    painter.text(
+        containing_block.left_top() + egui::vec2(2.0, 2.0),
+        egui::Align2::LEFT_TOP,
+        "containing block",
+        egui::FontId::proportional(11.0),
+        color,
+    );
+
+    let center = node_rect.center();
+    let width = containing_block.width();
+    let height = containing_block.height();
+    // (inset value, size it resolves against, margin on that side, start of the dimension line, direction from start towards the node)
+    for (inset, basis, margin, start, direction) in [
+        (
+            style.inset.left,
+            width,
+            layout.margin.left,
+            egui::pos2(containing_block.left(), center.y),
+            egui::vec2(1.0, 0.0),
+        ),
+        (
+            style.inset.right,
+            width,
+            layout.margin.right,
+            egui::pos2(containing_block.right(), center.y),
+            egui::vec2(-1.0, 0.0),
+        ),
+        (
+            style.inset.top,
+            height,
+            layout.margin.top,
+            egui::pos2(center.x, containing_block.top()),
+            egui::vec2(0.0, 1.0),
+        ),
+        (
+            style.inset.bottom,
+            height,
+            layout.margin.bottom,
+            egui::pos2(center.x, containing_block.bottom()),
+            egui::vec2(0.0, -1.0),
+        ),
+    ] {
+        let (resolved, mut text) = match inset {
+            LengthPercentageAuto::Auto => continue,
+            LengthPercentageAuto::Length(length) => (length, format!("{length}px")),
+            LengthPercentageAuto::Percent(percent) => (
+                percent * basis,
+                format!("{:.1}% = {:.1}px", percent * 100.0, percent * basis),
+            ),
+        };
+        let end = start + direction * resolved;
+        painter.line_segment([start, end], stroke);
+        // small ticks at both ends of the dimension line
+        let tick = egui::vec2(direction.y, direction.x) * 4.0;
+        painter.line_segment([start - tick, start + tick], stroke);
+        painter.line_segment([end - tick, end + tick], stroke);
+        if margin != 0.0 {
+            let node_edge = end + direction * margin;
+            painter.add(egui::Shape::dashed_line(
+                &[end, node_edge],
+                stroke,
+                3.0,
+                2.0,
+            ));
+            painter.line_segment([node_edge - tick, node_edge + tick], stroke);
+            text += &format!(" + {margin}px margin");
         }
+        let galley = painter.layout_no_wrap(text, egui::FontId::monospace(11.0), Color32::WHITE);
+        let label_rect =
+            egui::Align2::CENTER_CENTER.anchor_size(start + (end - start) / 2.0, galley.size());
+        painter.rect_filled(label_rect.expand(2.0), 2.0, color);
+        painter.galley(label_rect.min, galley, Color32::WHITE);
     }
 }
+/// The storage key of [`PaintSettings`]
+const PAINT_SETTINGS_KEY: &str = "paint_settings";
+/// The storage key of the current [`Document`]
+const DOCUMENT_KEY: &str = "document";
+/// The storage key of the [`FuzzFailure`]s
+const FUZZ_FAILURES_KEY: &str = "fuzz_failures";
 impl TemplateApp {
     /// Called once before the first frame.
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
-        Default::default()
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let mut app = Self::default();
+        if let Some(storage) = cc.storage {
+            if let Some(paint_settings) = eframe::get_value(storage, PAINT_SETTINGS_KEY) {
+                app.editor.paint_settings = paint_settings;
+            }
+            if let Some(document) = eframe::get_value::<Document>(storage, DOCUMENT_KEY) {
+                app.editor.load_document(&document);
+            }
+            if let Some(fuzz_failures) = eframe::get_value(storage, FUZZ_FAILURES_KEY) {
+                app.editor.fuzz_failures = fuzz_failures;
+            }
+        }
+        app
     }
 }
 
@@ -1018,4 +2877,10 @@ impl eframe::App for TemplateApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.editor.ui(ctx);
     }
+    /// Called by the framework to save state before shutdown.
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, PAINT_SETTINGS_KEY, &self.editor.paint_settings);
+        eframe::set_value(storage, DOCUMENT_KEY, &self.editor.document());
+        eframe::set_value(storage, FUZZ_FAILURES_KEY, &self.editor.fuzz_failures);
+    }
 }
@@ -0,0 +1,296 @@
+//! Times `compute_layout` on a document, with cold and warm caches and across a sweep of available widths.
+//! Used by the Benchmark window and by the `bench` command of the headless CLI.
+use std::collections::BTreeMap;
+
+use taffy::{AvailableSpace, Display, NodeId, Size, TaffyTree, TraversePartialTree};
+use web_time::{Duration, Instant};
+
+use crate::document::Document;
+use crate::layout_trace::LayoutTrace;
+use crate::stress::StressSettings;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchmarkSettings {
+    /// How many times the layout is timed for each width and cache state.
+    pub iterations: usize,
+    pub min_width: f32,
+    pub max_width: f32,
+    /// The number of widths from `min_width` to `max_width` (inclusive).
+    pub width_steps: usize,
+}
+impl Default for BenchmarkSettings {
+    fn default() -> Self {
+        Self {
+            iterations: 20,
+            min_width: 400.0,
+            max_width: 1600.0,
+            width_steps: 4,
+        }
+    }
+}
+impl BenchmarkSettings {
+    pub fn widths(&self) -> Vec<f32> {
+        if self.width_steps <= 1 {
+            return vec![self.min_width];
+        }
+        let step = (self.max_width - self.min_width) / (self.width_steps - 1) as f32;
+        (0..self.width_steps)
+            .map(|i| self.min_width + step * i as f32)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CacheState {
+    /// All the nodes are marked dirty before each layout, so nothing comes from the cache.
+    Cold,
+    /// The tree is laid out again without changes, so the caches of the previous layout are used.
+    Warm,
+}
+
+/// The timings of one width and cache state, in milliseconds.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BenchmarkRun {
+    pub width: f32,
+    pub cache: CacheState,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub p99_ms: f64,
+}
+
+/// The share of the layout time spent in the nodes of a display mode.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ModeShare {
+    /// flex, grid, block, leaf (a node without children) or none.
+    pub mode: &'static str,
+    pub calls: usize,
+    /// Between 0 and 1.
+    pub share: f64,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct BenchmarkReport {
+    pub node_count: usize,
+    pub iterations: usize,
+    pub runs: Vec<BenchmarkRun>,
+    /// From traced cold layouts at every width. Tracing adds overhead, so only the shares are meaningful.
+    pub modes: Vec<ModeShare>,
+}
+
+/// Lays out the document with the settings and reports the timings.
+/// The document is loaded into a new tree, so this doesn't touch the tree of the editor.
+pub fn run_benchmark(document: &Document, settings: &BenchmarkSettings) -> BenchmarkReport {
+    let (mut tree, root, _, style_variants) = document.to_tree();
+    let nodes = all_nodes(&tree, root);
+    let iterations = settings.iterations.max(1);
+    let mut runs = vec![];
+    let mut mode_time: BTreeMap<&'static str, (usize, Duration)> = BTreeMap::new();
+    for width in settings.widths() {
+        let available_space = Size {
+            width: AvailableSpace::Definite(width),
+            height: AvailableSpace::MinContent,
+        };
+        // the variants of the nodes are applied like in the responsive preview
+        style_variants.set_styles(&mut tree, Some(width));
+        for cache in [CacheState::Cold, CacheState::Warm] {
+            let mut durations: Vec<Duration> = (0..iterations)
+                .map(|_| {
+                    if cache == CacheState::Cold {
+                        for &node in &nodes {
+                            tree.mark_dirty(node).unwrap();
+                        }
+                    }
+                    let start = Instant::now();
+                    tree.compute_layout(root, available_space).unwrap();
+                    start.elapsed()
+                })
+                .collect();
+            durations.sort_unstable();
+            let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+            let p99_index = (durations.len() * 99).div_ceil(100) - 1;
+            runs.push(BenchmarkRun {
+                width,
+                cache,
+                min_ms: ms(durations[0]),
+                median_ms: ms(durations[durations.len() / 2]),
+                p99_ms: ms(durations[p99_index]),
+            });
+        }
+        // the time of each call minus the time of the calls it made, is the time spent in the node itself
+        let trace = LayoutTrace::record(&tree, root, available_space);
+        let mut self_time: Vec<Duration> = trace.calls.iter().map(|call| call.duration).collect();
+        for call in &trace.calls {
+            if let Some(caller) = call.caller {
+                self_time[caller] = self_time[caller].saturating_sub(call.duration);
+            }
+        }
+        for (call, time) in trace.calls.iter().zip(self_time) {
+            let entry = mode_time.entry(display_mode(&tree, call.node)).or_default();
+            entry.0 += 1;
+            entry.1 += time;
+        }
+    }
+    let total: Duration = mode_time.values().map(|(_, time)| *time).sum();
+    let mut modes: Vec<ModeShare> = mode_time
+        .into_iter()
+        .map(|(mode, (calls, time))| ModeShare {
+            mode,
+            calls,
+            share: time.as_secs_f64() / total.as_secs_f64().max(f64::EPSILON),
+        })
+        .collect();
+    modes.sort_by(|a, b| b.share.total_cmp(&a.share));
+    BenchmarkReport {
+        node_count: nodes.len(),
+        iterations,
+        runs,
+        modes,
+    }
+}
+
+fn all_nodes(tree: &TaffyTree, root: NodeId) -> Vec<NodeId> {
+    let mut nodes = vec![root];
+    let mut i = 0;
+    while i < nodes.len() {
+        nodes.extend(tree.child_ids(nodes[i]));
+        i += 1;
+    }
+    nodes
+}
+
+/// The layout algorithm that taffy uses for the node.
+fn display_mode(tree: &TaffyTree, node: NodeId) -> &'static str {
+    let display = tree
+        .style(node)
+        .map(|style| style.display)
+        .unwrap_or_default();
+    match display {
+        Display::None => "none",
+        _ if tree.child_count(node) == 0 => "leaf",
+        Display::Flex => "flex",
+        Display::Grid => "grid",
+        Display::Block => "block",
+    }
+}
+
+impl BenchmarkReport {
+    /// The report as an aligned plain text table.
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{} nodes, {} iterations per run\n\n{:>8}  {:<5}  {:>10}  {:>10}  {:>10}\n",
+            self.node_count, self.iterations, "width", "cache", "min ms", "median ms", "p99 ms"
+        );
+        for run in &self.runs {
+            let cache = match run.cache {
+                CacheState::Cold => "cold",
+                CacheState::Warm => "warm",
+            };
+            text += &format!(
+                "{:>8}  {cache:<5}  {:>10.3}  {:>10.3}  {:>10.3}\n",
+                run.width, run.min_ms, run.median_ms, run.p99_ms
+            );
+        }
+        text += "\ntime spent per display mode (cold layout):\n";
+        for mode in &self.modes {
+            text += &format!(
+                "{:<6} {:>5.1}%  {:>8} calls\n",
+                mode.mode,
+                mode.share * 100.0,
+                mode.calls
+            );
+        }
+        text
+    }
+}
+
+pub const CLI_USAGE: &str = "usage: taffy_play bench [DOCUMENT.json] [--iterations N] [--min-width W] [--max-width W] [--steps N] [--json]
+
+Lays out the document (exported from the Document menu) and prints the timings.
+Without a document, a stress tree with the default generator settings is used.";
+
+/// Runs the `bench` command of the CLI with the arguments after `bench`, and returns the report to print.
+pub fn run_cli(args: &[String]) -> Result<String, String> {
+    let mut settings = BenchmarkSettings::default();
+    let mut document_path = None;
+    let mut json = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| {
+            args.next()
+                .ok_or(format!("{name} needs a value\n\n{CLI_USAGE}"))
+        };
+        let parse_error = |name: &str| format!("invalid value for {name}\n\n{CLI_USAGE}");
+        match arg.as_str() {
+            "--iterations" => {
+                settings.iterations = value(arg)?.parse().map_err(|_| parse_error(arg))?;
+            }
+            "--min-width" => {
+                settings.min_width = value(arg)?.parse().map_err(|_| parse_error(arg))?;
+            }
+            "--max-width" => {
+                settings.max_width = value(arg)?.parse().map_err(|_| parse_error(arg))?;
+            }
+            "--steps" => {
+                settings.width_steps = value(arg)?.parse().map_err(|_| parse_error(arg))?;
+            }
+            "--json" => json = true,
+            "--help" | "-h" => return Ok(CLI_USAGE.to_string()),
+            path if !path.starts_with("--") && document_path.is_none() => {
+                document_path = Some(path.to_string());
+            }
+            _ => return Err(format!("unexpected argument: {arg}\n\n{CLI_USAGE}")),
+        }
+    }
+    let document = match document_path {
+        Some(path) => {
+            let text = std::fs::read_to_string(&path).map_err(|e| format!("{path}: {e}"))?;
+            serde_json::from_str(&text).map_err(|e| format!("{path}: {e}"))?
+        }
+        None => StressSettings::default().generate(),
+    };
+    let report = run_benchmark(&document, &settings);
+    if json {
+        serde_json::to_string_pretty(&report).map_err(|e| e.to_string())
+    } else {
+        Ok(report.to_text())
+    }
+}
+
+/// The settings and the last report. Returns true if "run" was clicked.
+pub fn benchmark_ui(
+    ui: &mut egui::Ui,
+    settings: &mut BenchmarkSettings,
+    report: &Option<BenchmarkReport>,
+) -> bool {
+    egui::Grid::new("benchmark settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("iterations");
+            ui.add(egui::DragValue::new(&mut settings.iterations).range(1..=1000));
+            ui.end_row();
+            ui.label("widths");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.min_width).range(0.0..=f32::MAX));
+                ui.label("to");
+                ui.add(egui::DragValue::new(&mut settings.max_width).range(0.0..=f32::MAX));
+                ui.label("in");
+                ui.add(egui::DragValue::new(&mut settings.width_steps).range(1..=20));
+                ui.label("steps");
+            });
+            ui.end_row();
+        });
+    ui.label("cold: all nodes are marked dirty before each layout. warm: the same layout again, answered by the caches.");
+    let run = ui
+        .button("run")
+        .on_hover_text("lays out a copy of the current document. The ui freezes until it's done.")
+        .clicked();
+    if let Some(report) = report {
+        let text = report.to_text();
+        if ui.button("📋 copy").clicked() {
+            ui.ctx().copy_text(text.clone());
+        }
+        ui.label(egui::RichText::new(text).monospace());
+    }
+    run
+}
@@ -0,0 +1,582 @@
+//! Parsing and printing style values in css syntax (`10px`, `50%`, `auto`, `1fr`, `minmax(100px, 1fr)` etc..)
+use taffy::{
+    prelude::line, Dimension, GridPlacement, GridTrackRepetition, LengthPercentage,
+    LengthPercentageAuto, Line, MaxTrackSizingFunction, MinMax, MinTrackSizingFunction,
+    NonRepeatedTrackSizingFunction, Rect, TrackSizingFunction,
+};
+
+/// A style value that can be written as css text.
+pub trait CssValue: Sized {
+    /// Parses the css text. The error is a short message that can be shown next to the field.
+    fn parse_css(text: &str) -> Result<Self, String>;
+    fn to_css(&self) -> String;
+}
+
+/// A single css value, before it is converted into one of the taffy types.
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Length(f32),
+    /// The percentage divided by 100, like taffy's `Percent`
+    Percent(f32),
+    Fraction(f32),
+    Auto,
+    MinContent,
+    MaxContent,
+    FitContent(LengthPercentage),
+    MinMax(Box<Token>, Box<Token>),
+}
+
+impl Token {
+    fn parse(text: &str) -> Result<Self, String> {
+        let text = text.trim().to_ascii_lowercase();
+        if text.is_empty() {
+            return Err("empty value".to_string());
+        }
+        if let Some(args) = function_args(&text, "minmax")? {
+            let [min, max] = args.as_slice() else {
+                return Err("minmax expects 2 values".to_string());
+            };
+            return Ok(Token::MinMax(
+                Box::new(Token::parse(min)?),
+                Box::new(Token::parse(max)?),
+            ));
+        }
+        if let Some(args) = function_args(&text, "fit-content")? {
+            let [limit] = args.as_slice() else {
+                return Err("fit-content expects 1 value".to_string());
+            };
+            return Ok(Token::FitContent(LengthPercentage::parse_css(limit)?));
+        }
+        match text.as_str() {
+            "auto" => return Ok(Token::Auto),
+            "min-content" => return Ok(Token::MinContent),
+            "max-content" => return Ok(Token::MaxContent),
+            _ => {}
+        }
+        let number_end = text
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+')))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(number_end);
+        let number: f32 = number
+            .parse()
+            .map_err(|_| format!("`{text}` is not a valid value"))?;
+        // too many digits parse as infinity, which would break the layout
+        if !number.is_finite() {
+            return Err(format!("`{text}` is too large"));
+        }
+        match unit.trim() {
+            "px" => Ok(Token::Length(number)),
+            "%" => Ok(Token::Percent(number / 100.0)),
+            "fr" => Ok(Token::Fraction(number)),
+            // like css, zero doesn't need a unit
+            "" if number == 0.0 => Ok(Token::Length(0.0)),
+            "" => Err(format!("`{text}` is missing a unit (px, % or fr)")),
+            unit => Err(format!("unknown unit `{unit}`")),
+        }
+    }
+    fn kind(&self) -> &'static str {
+        match self {
+            Token::Length(_) => "a length",
+            Token::Percent(_) => "a percentage",
+            Token::Fraction(_) => "a fraction (fr)",
+            Token::Auto => "auto",
+            Token::MinContent => "min-content",
+            Token::MaxContent => "max-content",
+            Token::FitContent(_) => "fit-content()",
+            Token::MinMax(_, _) => "minmax()",
+        }
+    }
+    /// The error for a value that is valid css, but not for this type.
+    fn unexpected<T>(&self, expected: &str) -> Result<T, String> {
+        Err(format!("expected {expected}, found {}", self.kind()))
+    }
+}
+
+/// If `text` is a call to the function `name`, returns its comma separated arguments.
+fn function_args<'a>(text: &'a str, name: &str) -> Result<Option<Vec<&'a str>>, String> {
+    let Some(rest) = text.strip_prefix(name) else {
+        return Ok(None);
+    };
+    let Some(inner) = rest
+        .trim_start()
+        .strip_prefix('(')
+        .and_then(|rest| rest.strip_suffix(')'))
+    else {
+        return Err(format!("expected `{name}(...)`"));
+    };
+    Ok(Some(split_top_level(inner, |c| c == ',')))
+}
+
+/// Splits `text` at the separators that aren't inside parentheses. Empty parts are skipped.
+fn split_top_level(text: &str, is_separator: impl Fn(char) -> bool) -> Vec<&str> {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if depth == 0 && is_separator(c) => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+    }
+    parts.push(&text[start..]);
+    parts.retain(|part| !part.trim().is_empty());
+    parts
+}
+
+/// Formats a number without a trailing `.0`, so that we print `10px` instead of `10.0px`.
+/// It's rounded to 4 decimals, to hide the float noise of percentages (0.07 * 100 = 7.0000005).
+fn number_css(value: f32) -> String {
+    format!("{}", (value * 10_000.0).round() / 10_000.0)
+}
+
+impl CssValue for LengthPercentage {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        match Token::parse(text)? {
+            Token::Length(value) => Ok(LengthPercentage::Length(value)),
+            Token::Percent(value) => Ok(LengthPercentage::Percent(value)),
+            token => token.unexpected("a length or percentage"),
+        }
+    }
+    fn to_css(&self) -> String {
+        match *self {
+            LengthPercentage::Length(value) => format!("{}px", number_css(value)),
+            LengthPercentage::Percent(value) => format!("{}%", number_css(value * 100.0)),
+        }
+    }
+}
+
+impl CssValue for LengthPercentageAuto {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        match Token::parse(text)? {
+            Token::Length(value) => Ok(LengthPercentageAuto::Length(value)),
+            Token::Percent(value) => Ok(LengthPercentageAuto::Percent(value)),
+            Token::Auto => Ok(LengthPercentageAuto::Auto),
+            token => token.unexpected("a length, percentage or auto"),
+        }
+    }
+    fn to_css(&self) -> String {
+        match *self {
+            LengthPercentageAuto::Length(value) => LengthPercentage::Length(value).to_css(),
+            LengthPercentageAuto::Percent(value) => LengthPercentage::Percent(value).to_css(),
+            LengthPercentageAuto::Auto => "auto".to_string(),
+        }
+    }
+}
+
+impl CssValue for Dimension {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        match Token::parse(text)? {
+            Token::Length(value) => Ok(Dimension::Length(value)),
+            Token::Percent(value) => Ok(Dimension::Percent(value)),
+            Token::Auto => Ok(Dimension::Auto),
+            token => token.unexpected("a length, percentage or auto"),
+        }
+    }
+    fn to_css(&self) -> String {
+        match *self {
+            Dimension::Length(value) => LengthPercentage::Length(value).to_css(),
+            Dimension::Percent(value) => LengthPercentage::Percent(value).to_css(),
+            Dimension::Auto => "auto".to_string(),
+        }
+    }
+}
+
+impl CssValue for MinTrackSizingFunction {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        min_track_from_token(Token::parse(text)?)
+    }
+    fn to_css(&self) -> String {
+        match *self {
+            MinTrackSizingFunction::Fixed(value) => value.to_css(),
+            MinTrackSizingFunction::MinContent => "min-content".to_string(),
+            MinTrackSizingFunction::MaxContent => "max-content".to_string(),
+            MinTrackSizingFunction::Auto => "auto".to_string(),
+        }
+    }
+}
+fn min_track_from_token(token: Token) -> Result<MinTrackSizingFunction, String> {
+    match token {
+        Token::Length(value) => Ok(MinTrackSizingFunction::Fixed(LengthPercentage::Length(
+            value,
+        ))),
+        Token::Percent(value) => Ok(MinTrackSizingFunction::Fixed(LengthPercentage::Percent(
+            value,
+        ))),
+        Token::Auto => Ok(MinTrackSizingFunction::Auto),
+        Token::MinContent => Ok(MinTrackSizingFunction::MinContent),
+        Token::MaxContent => Ok(MinTrackSizingFunction::MaxContent),
+        token => token.unexpected("a length, percentage, auto, min-content or max-content"),
+    }
+}
+
+impl CssValue for MaxTrackSizingFunction {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        max_track_from_token(Token::parse(text)?)
+    }
+    fn to_css(&self) -> String {
+        match *self {
+            MaxTrackSizingFunction::Fixed(value) => value.to_css(),
+            MaxTrackSizingFunction::MinContent => "min-content".to_string(),
+            MaxTrackSizingFunction::MaxContent => "max-content".to_string(),
+            MaxTrackSizingFunction::FitContent(limit) => {
+                format!("fit-content({})", limit.to_css())
+            }
+            MaxTrackSizingFunction::Auto => "auto".to_string(),
+            MaxTrackSizingFunction::Fraction(value) => format!("{}fr", number_css(value)),
+        }
+    }
+}
+fn max_track_from_token(token: Token) -> Result<MaxTrackSizingFunction, String> {
+    match token {
+        Token::Length(value) => Ok(MaxTrackSizingFunction::Fixed(LengthPercentage::Length(
+            value,
+        ))),
+        Token::Percent(value) => Ok(MaxTrackSizingFunction::Fixed(LengthPercentage::Percent(
+            value,
+        ))),
+        Token::Fraction(value) => Ok(MaxTrackSizingFunction::Fraction(value)),
+        Token::Auto => Ok(MaxTrackSizingFunction::Auto),
+        Token::MinContent => Ok(MaxTrackSizingFunction::MinContent),
+        Token::MaxContent => Ok(MaxTrackSizingFunction::MaxContent),
+        Token::FitContent(limit) => Ok(MaxTrackSizingFunction::FitContent(limit)),
+        token @ Token::MinMax(_, _) => token.unexpected("a single track size"),
+    }
+}
+
+/// A grid track size, either a single value (`100px`, `1fr`) or `minmax(min, max)`.
+impl CssValue for NonRepeatedTrackSizingFunction {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        match Token::parse(text)? {
+            Token::MinMax(min, max) => Ok(MinMax {
+                min: min_track_from_token(*min)?,
+                max: max_track_from_token(*max)?,
+            }),
+            token => {
+                let max = max_track_from_token(token)?;
+                Ok(MinMax {
+                    min: single_track_min(max),
+                    max,
+                })
+            }
+        }
+    }
+    fn to_css(&self) -> String {
+        if self.min == single_track_min(self.max) {
+            self.max.to_css()
+        } else {
+            format!("minmax({}, {})", self.min.to_css(), self.max.to_css())
+        }
+    }
+}
+/// The min track sizing function of a track that is given a single value in css.
+fn single_track_min(max: MaxTrackSizingFunction) -> MinTrackSizingFunction {
+    match max {
+        MaxTrackSizingFunction::Fixed(value) => MinTrackSizingFunction::Fixed(value),
+        MaxTrackSizingFunction::MinContent => MinTrackSizingFunction::MinContent,
+        MaxTrackSizingFunction::MaxContent => MinTrackSizingFunction::MaxContent,
+        MaxTrackSizingFunction::FitContent(_)
+        | MaxTrackSizingFunction::Auto
+        | MaxTrackSizingFunction::Fraction(_) => MinTrackSizingFunction::Auto,
+    }
+}
+
+/// A grid track, either a single track or `repeat(count, tracks)` where count is a number, `auto-fill` or `auto-fit`.
+impl CssValue for TrackSizingFunction {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        let text = text.trim().to_ascii_lowercase();
+        let Some(args) = function_args(&text, "repeat")? else {
+            return NonRepeatedTrackSizingFunction::parse_css(&text)
+                .map(TrackSizingFunction::Single);
+        };
+        let [repetition, tracks] = args.as_slice() else {
+            return Err("repeat expects a count and the tracks".to_string());
+        };
+        let repetition = match repetition.trim() {
+            "auto-fill" => GridTrackRepetition::AutoFill,
+            "auto-fit" => GridTrackRepetition::AutoFit,
+            count => GridTrackRepetition::Count(count.parse().map_err(|_| {
+                format!("the repeat count `{count}` is not a number, auto-fill or auto-fit")
+            })?),
+        };
+        Ok(TrackSizingFunction::Repeat(
+            repetition,
+            Vec::<NonRepeatedTrackSizingFunction>::parse_css(tracks)?,
+        ))
+    }
+    fn to_css(&self) -> String {
+        match self {
+            TrackSizingFunction::Single(track) => track.to_css(),
+            TrackSizingFunction::Repeat(repetition, tracks) => {
+                let repetition = match repetition {
+                    GridTrackRepetition::AutoFill => "auto-fill".to_string(),
+                    GridTrackRepetition::AutoFit => "auto-fit".to_string(),
+                    GridTrackRepetition::Count(count) => count.to_string(),
+                };
+                format!("repeat({repetition}, {})", tracks.to_css())
+            }
+        }
+    }
+}
+
+/// A track list of `grid_template_rows/columns`, separated by spaces. `none` is an empty list.
+impl CssValue for Vec<TrackSizingFunction> {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        track_list_parse(text)
+    }
+    fn to_css(&self) -> String {
+        track_list_css(self)
+    }
+}
+/// A track list of `grid_auto_rows/columns`, separated by spaces. `none` is an empty list.
+impl CssValue for Vec<NonRepeatedTrackSizingFunction> {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        track_list_parse(text)
+    }
+    fn to_css(&self) -> String {
+        track_list_css(self)
+    }
+}
+fn track_list_parse<T: CssValue>(text: &str) -> Result<Vec<T>, String> {
+    if text.trim().eq_ignore_ascii_case("none") {
+        return Ok(vec![]);
+    }
+    split_top_level(text, char::is_whitespace)
+        .into_iter()
+        .map(T::parse_css)
+        .collect()
+}
+fn track_list_css<T: CssValue>(tracks: &[T]) -> String {
+    if tracks.is_empty() {
+        return "none".to_string();
+    }
+    tracks
+        .iter()
+        .map(CssValue::to_css)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The placement of a grid item: `start / end`, where both are `auto`, a line number or `span n`.
+/// The end can be omitted, which makes it `auto`.
+impl CssValue for Line<GridPlacement> {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        fn placement_parse(text: &str) -> Result<GridPlacement, String> {
+            let text = text.trim().to_ascii_lowercase();
+            if text == "auto" {
+                return Ok(GridPlacement::Auto);
+            }
+            if let Some(count) = text.strip_prefix("span") {
+                return match count.trim().parse() {
+                    Ok(count) if count > 0 => Ok(GridPlacement::Span(count)),
+                    _ => Err(format!("`{text}` should be span and a positive number")),
+                };
+            }
+            match text.parse::<i16>() {
+                Ok(0) => {
+                    Err("line 0 is not valid, lines start at 1 (or -1 from the end)".to_string())
+                }
+                Ok(index) => Ok(line::<GridPlacement>(index)),
+                Err(_) => Err(format!("`{text}` is not auto, a line number or span")),
+            }
+        }
+        let mut parts = text.split('/');
+        let start = placement_parse(parts.next().unwrap_or_default())?;
+        let end = match parts.next() {
+            Some(end) => placement_parse(end)?,
+            None => GridPlacement::Auto,
+        };
+        if parts.next().is_some() {
+            return Err("expected `start / end`".to_string());
+        }
+        Ok(Line { start, end })
+    }
+    fn to_css(&self) -> String {
+        fn placement_css(placement: GridPlacement) -> String {
+            match placement {
+                GridPlacement::Auto => "auto".to_string(),
+                GridPlacement::Line(line) => line.as_i16().to_string(),
+                GridPlacement::Span(count) => format!("span {count}"),
+            }
+        }
+        if self.end == GridPlacement::Auto {
+            placement_css(self.start)
+        } else {
+            format!(
+                "{} / {}",
+                placement_css(self.start),
+                placement_css(self.end)
+            )
+        }
+    }
+}
+
+/// The css shorthand of margin, padding, border and inset: `top [right [bottom [left]]]`, e.g. `10px 20px` or `0 auto`.
+impl<T: CssValue + Copy + PartialEq> CssValue for Rect<T> {
+    fn parse_css(text: &str) -> Result<Self, String> {
+        let values = text
+            .split_whitespace()
+            .map(T::parse_css)
+            .collect::<Result<Vec<T>, String>>()?;
+        let (top, right, bottom, left) = match *values.as_slice() {
+            [all] => (all, all, all, all),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left] => (top, right, bottom, left),
+            _ => return Err("expected 1 to 4 values".to_string()),
+        };
+        Ok(Rect {
+            left,
+            right,
+            top,
+            bottom,
+        })
+    }
+    fn to_css(&self) -> String {
+        // the shortest shorthand that gives the same rect
+        let values = if self.left != self.right {
+            vec![self.top, self.right, self.bottom, self.left]
+        } else if self.top != self.bottom {
+            vec![self.top, self.right, self.bottom]
+        } else if self.top != self.right {
+            vec![self.top, self.right]
+        } else {
+            vec![self.top]
+        };
+        values
+            .iter()
+            .map(CssValue::to_css)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// A text field that accepts the value in css syntax, shown next to the regular widgets of a value.
+/// Valid input is applied while typing. Invalid input is kept (with an inline error) while the field has focus,
+/// and doesn't change the value. It's dropped when the field loses focus, so it can't show up for another node.
+pub fn css_text_ui<T: CssValue>(ui: &mut egui::Ui, value: &mut T) {
+    let id = ui.make_persistent_id("css text");
+    let mut text = ui
+        .data(|data| data.get_temp::<String>(id))
+        .unwrap_or_else(|| value.to_css());
+    let res = ui.add(
+        egui::TextEdit::singleline(&mut text)
+            .id(id)
+            .desired_width(90.0)
+            .clip_text(false)
+            .hint_text("css"),
+    );
+    match T::parse_css(&text) {
+        Ok(parsed) => {
+            if res.changed() {
+                *value = parsed;
+            }
+        }
+        Err(error) => {
+            if res.has_focus() {
+                ui.colored_label(ui.visuals().error_fg_color, format!("⚠ {error}"));
+            }
+        }
+    }
+    if res.has_focus() {
+        ui.data_mut(|data| data.insert_temp(id, text));
+    } else {
+        // when not editing, the text follows the value (which may be changed by other widgets)
+        ui.data_mut(|data| data.remove::<String>(id));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Parsing the printed value gives the same value back.
+    fn assert_round_trip<T: CssValue + PartialEq + std::fmt::Debug>(value: T) {
+        let css = value.to_css();
+        assert_eq!(T::parse_css(&css), Ok(value), "round trip of `{css}`");
+    }
+    /// Printing the parsed text gives the same text back.
+    fn assert_canonical<T: CssValue>(text: &str) {
+        let value = T::parse_css(text).unwrap_or_else(|error| panic!("`{text}`: {error}"));
+        assert_eq!(value.to_css(), text);
+    }
+
+    #[test]
+    fn lengths_round_trip() {
+        assert_round_trip(LengthPercentage::Length(10.0));
+        assert_round_trip(LengthPercentage::Length(-2.5));
+        assert_round_trip(LengthPercentage::Percent(0.5));
+        assert_round_trip(LengthPercentageAuto::Auto);
+        assert_round_trip(LengthPercentageAuto::Length(0.0));
+        assert_round_trip(Dimension::Auto);
+        assert_round_trip(Dimension::Percent(1.0));
+        assert_round_trip(Dimension::Length(1e6));
+        assert_eq!(
+            LengthPercentage::parse_css("0"),
+            Ok(LengthPercentage::Length(0.0))
+        );
+    }
+
+    #[test]
+    fn grid_values_round_trip() {
+        for text in [
+            "100px",
+            "1fr",
+            "auto",
+            "min-content",
+            "fit-content(50%)",
+            "minmax(100px, 1fr)",
+            "minmax(auto, max-content)",
+        ] {
+            assert_canonical::<NonRepeatedTrackSizingFunction>(text);
+        }
+        for text in [
+            "none",
+            "100px 1fr",
+            "repeat(3, 1fr)",
+            "repeat(auto-fill, minmax(80px, 1fr)) 20px",
+            "repeat(auto-fit, 100px 2fr)",
+        ] {
+            assert_canonical::<Vec<TrackSizingFunction>>(text);
+        }
+        for text in ["auto", "1", "-1", "span 2", "1 / -1", "3 / span 2"] {
+            assert_canonical::<Line<GridPlacement>>(text);
+        }
+    }
+
+    #[test]
+    fn rect_shorthands() {
+        for text in ["10px", "10px 20px", "10px 20px 30px", "10px 20px 30px 40px"] {
+            assert_canonical::<Rect<LengthPercentage>>(text);
+        }
+        assert_canonical::<Rect<LengthPercentageAuto>>("0px auto");
+        let rect = Rect::<LengthPercentageAuto>::parse_css("1px 2px 3px").unwrap();
+        assert_eq!(rect.left, LengthPercentageAuto::Length(2.0));
+        assert_eq!(rect.bottom, LengthPercentageAuto::Length(3.0));
+    }
+
+    #[test]
+    fn invalid_values_are_errors() {
+        assert!(LengthPercentage::parse_css("").is_err());
+        assert!(LengthPercentage::parse_css("10").is_err());
+        assert!(LengthPercentage::parse_css("10em").is_err());
+        assert!(LengthPercentage::parse_css("auto").is_err());
+        assert!(LengthPercentage::parse_css("px").is_err());
+        assert!(Dimension::parse_css("1fr").is_err());
+        // numbers that don't fit in an f32
+        assert!(Dimension::parse_css(&format!("1{}px", "0".repeat(40))).is_err());
+        assert!(Dimension::parse_css("1e40px").is_err());
+        assert!(NonRepeatedTrackSizingFunction::parse_css("minmax(1fr)").is_err());
+        assert!(NonRepeatedTrackSizingFunction::parse_css("minmax(1fr, 1fr").is_err());
+        assert!(NonRepeatedTrackSizingFunction::parse_css("repeat(2, 1fr)").is_err());
+        assert!(TrackSizingFunction::parse_css("repeat(many, 1fr)").is_err());
+        assert!(Line::<GridPlacement>::parse_css("0").is_err());
+        assert!(Line::<GridPlacement>::parse_css("span 0").is_err());
+        assert!(Line::<GridPlacement>::parse_css("1 / 2 / 3").is_err());
+        assert!(Rect::<LengthPercentage>::parse_css("1px 2px 3px 4px 5px").is_err());
+    }
+}
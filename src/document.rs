@@ -0,0 +1,131 @@
+use std::collections::{BTreeMap, HashMap};
+
+use taffy::{NodeId, Style, TaffyTree};
+
+use crate::style_class::{ClassMembership, StyleClasses};
+use crate::style_variant::{NodeVariants, StyleVariant, StyleVariants};
+
+/// A snapshot of a node tree that can be loaded into the editor, saved or shared as json.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Document {
+    /// The style of new nodes added to this document.
+    #[serde(default)]
+    pub default_style: Style,
+    #[serde(default)]
+    pub classes: BTreeMap<String, Style>,
+    pub root: DocumentNode,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DocumentNode {
+    /// The base style, if the node has variants.
+    pub style: Style,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub class: Option<ClassMembership>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<StyleVariant>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<DocumentNode>,
+}
+
+impl DocumentNode {
+    pub fn leaf(style: Style) -> Self {
+        Self {
+            style,
+            class: None,
+            variants: vec![],
+            children: vec![],
+        }
+    }
+    pub fn with_children(style: Style, children: Vec<DocumentNode>) -> Self {
+        Self {
+            style,
+            class: None,
+            variants: vec![],
+            children,
+        }
+    }
+}
+
+impl Document {
+    /// Creates the nodes of this document in a new tree and returns the tree + root node + style classes + style variants.
+    /// The nodes start with their base styles.
+    pub fn to_tree(&self) -> (TaffyTree, NodeId, StyleClasses, StyleVariants) {
+        fn add_recursive(
+            tree: &mut TaffyTree,
+            members: &mut HashMap<NodeId, ClassMembership>,
+            style_variants: &mut StyleVariants,
+            node: &DocumentNode,
+        ) -> NodeId {
+            let children: Vec<NodeId> = node
+                .children
+                .iter()
+                .map(|child| add_recursive(tree, members, style_variants, child))
+                .collect();
+            let node_id = tree
+                .new_with_children(node.style.clone(), &children)
+                .unwrap();
+            if let Some(membership) = node.class.clone() {
+                members.insert(node_id, membership);
+            }
+            if !node.variants.is_empty() {
+                style_variants.nodes.insert(
+                    node_id,
+                    NodeVariants {
+                        base: node.style.clone(),
+                        variants: node.variants.clone(),
+                        active: None,
+                    },
+                );
+            }
+            node_id
+        }
+        let mut tree = TaffyTree::new();
+        let mut members = HashMap::new();
+        let mut style_variants = StyleVariants::default();
+        let root = add_recursive(&mut tree, &mut members, &mut style_variants, &self.root);
+        let style_classes = StyleClasses {
+            classes: self.classes.clone(),
+            members,
+        };
+        (tree, root, style_classes, style_variants)
+    }
+    /// Takes a snapshot of the node tree starting at `root`.
+    pub fn from_tree(
+        tree: &TaffyTree,
+        root: NodeId,
+        default_style: &Style,
+        style_classes: &StyleClasses,
+        style_variants: &StyleVariants,
+    ) -> Self {
+        fn node_recursive(
+            tree: &TaffyTree,
+            style_classes: &StyleClasses,
+            style_variants: &StyleVariants,
+            node_id: NodeId,
+        ) -> DocumentNode {
+            let node_variants = style_variants.nodes.get(&node_id);
+            DocumentNode {
+                style: match node_variants {
+                    Some(node_variants) => node_variants.base.clone(),
+                    None => tree.style(node_id).cloned().unwrap_or_default(),
+                },
+                class: style_classes.members.get(&node_id).cloned(),
+                variants: node_variants
+                    .map(|node_variants| node_variants.variants.clone())
+                    .unwrap_or_default(),
+                children: tree
+                    .children(node_id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|child| node_recursive(tree, style_classes, style_variants, child))
+                    .collect(),
+            }
+        }
+        Self {
+            default_style: default_style.clone(),
+            classes: style_classes.classes.clone(),
+            root: node_recursive(tree, style_classes, style_variants, root),
+        }
+    }
+}
@@ -0,0 +1,738 @@
+//! Mutates random style fields of a document and checks that the layout stays sane:
+//! no panics, no NaN or infinite values, no negative sizes, and zero sized `Display::None` nodes.
+//! Failing documents are minimized to the fewest nodes and style fields that still fail.
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+use taffy::{
+    AlignContent, AlignItems, AvailableSpace, BoxSizing, Dimension, Display, FlexDirection,
+    FlexWrap, GridAutoFlow, Layout, LengthPercentage, LengthPercentageAuto, NodeId, Overflow,
+    Position, Rect, Size, Style, TaffyTree, TextAlign, TraversePartialTree,
+};
+
+use crate::css::CssValue;
+use crate::document::{Document, DocumentNode};
+use crate::stress::Rng;
+use crate::style_field::StyleField;
+
+/// Each check lays out the document in all of these.
+const AVAILABLE_SPACES: [Size<AvailableSpace>; 2] = [
+    Size {
+        width: AvailableSpace::MinContent,
+        height: AvailableSpace::MinContent,
+    },
+    Size {
+        width: AvailableSpace::Definite(800.0),
+        height: AvailableSpace::MaxContent,
+    },
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FuzzSettings {
+    pub seed: u64,
+    /// How many mutations are made (and checked) in a run.
+    pub iterations: usize,
+    /// Fuzz a generated stress tree instead of the current document.
+    pub use_stress_tree: bool,
+}
+impl Default for FuzzSettings {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            iterations: 200,
+            use_stress_tree: false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum InvariantKind {
+    /// `compute_layout` panicked (or returned an error).
+    Panic,
+    /// A layout value is NaN or infinite.
+    NotFinite,
+    NegativeSize,
+    /// A `Display::None` node (or a child of one) has a non-zero size.
+    HiddenNotZero,
+}
+
+/// A broken invariant.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Violation {
+    pub kind: InvariantKind,
+    /// Which node (as its path of child indices from the root) and which layout value broke it.
+    pub message: String,
+}
+
+/// A minimized document that breaks an invariant.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct FuzzFailure {
+    pub violation: Violation,
+    /// The seed of the run, and the iteration in which the failure was found.
+    pub seed: u64,
+    pub iteration: usize,
+    pub document: Document,
+    /// The minimization ran out of checks, so the document may still have nodes or fields that don't matter.
+    #[serde(default)]
+    pub partially_minimized: bool,
+}
+
+/// Runs the fuzzer on `document`. Every mutation that breaks an invariant is minimized and returned,
+/// and then undone so that the next mutations start from a working document.
+pub fn fuzz(document: &Document, settings: &FuzzSettings) -> Vec<FuzzFailure> {
+    let mut rng = Rng(settings.seed);
+    let mut document = document.clone();
+    let mut failures: Vec<FuzzFailure> = vec![];
+    let _silence_panics = SilencePanics::new();
+    for iteration in 0..settings.iterations {
+        let mut mutated = document.clone();
+        for _ in 0..1 + rng.below(3) {
+            let node = rng.below(node_count(&mutated.root));
+            let field = *rng.pick(StyleField::ALL);
+            let random = random_style(&mut rng);
+            field.copy(&random, &mut nth_node(&mut mutated.root, node).style);
+        }
+        match check(&mutated) {
+            Ok(()) => document = mutated,
+            Err(violation) => {
+                let (minimized, complete) = minimize(&mutated, violation.kind, MAX_MINIMIZE_CHECKS);
+                let violation = check(&minimized).err().unwrap_or(violation);
+                // the same bug tends to be found many times, and to minimize to the same document
+                let json = serde_json::to_string(&minimized).unwrap_or_default();
+                if !failures.iter().any(|failure| {
+                    failure.violation.kind == violation.kind
+                        && serde_json::to_string(&failure.document).unwrap_or_default() == json
+                }) {
+                    failures.push(FuzzFailure {
+                        violation,
+                        seed: settings.seed,
+                        iteration,
+                        document: minimized,
+                        partially_minimized: !complete,
+                    });
+                }
+            }
+        }
+    }
+    failures
+}
+
+/// Keeps the panics from being printed to the console while it's alive, as they are reported as failures.
+/// The previous panic hook is restored when it's dropped, also when unwinding.
+struct SilencePanics {
+    restore: Option<Box<dyn FnOnce()>>,
+}
+impl SilencePanics {
+    fn new() -> Self {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        Self {
+            restore: Some(Box::new(move || std::panic::set_hook(hook))),
+        }
+    }
+}
+impl Drop for SilencePanics {
+    fn drop(&mut self) {
+        if let Some(restore) = self.restore.take() {
+            restore();
+        }
+    }
+}
+
+/// Lays out the document and checks the invariants.
+pub fn check(document: &Document) -> Result<(), Violation> {
+    let (mut tree, root, _, _) = document.to_tree();
+    for available_space in AVAILABLE_SPACES {
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            tree.compute_layout(root, available_space)
+        }));
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => {
+                return Err(Violation {
+                    kind: InvariantKind::Panic,
+                    message: format!("compute_layout returned an error: {error}"),
+                })
+            }
+            Err(payload) => {
+                let message = payload
+                    .downcast_ref::<&str>()
+                    .map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_default();
+                return Err(Violation {
+                    kind: InvariantKind::Panic,
+                    message: format!("compute_layout panicked: {message}"),
+                });
+            }
+        }
+        check_recursive(&tree, root, &mut vec![], false)?;
+    }
+    Ok(())
+}
+
+/// `path` is the child indices from the root to `node`.
+fn check_recursive(
+    tree: &TaffyTree,
+    node: NodeId,
+    path: &mut Vec<usize>,
+    hidden: bool,
+) -> Result<(), Violation> {
+    let layout = tree.layout(node).unwrap();
+    let style = tree.style(node).unwrap();
+    if let Some((name, value)) = layout_values(layout)
+        .into_iter()
+        .find(|(_, value)| !value.is_finite())
+    {
+        return Err(Violation {
+            kind: InvariantKind::NotFinite,
+            message: format!("{}: {name} is {value}", node_text(path, style)),
+        });
+    }
+    for (name, value) in [
+        ("size.width", layout.size.width),
+        ("size.height", layout.size.height),
+    ] {
+        if value < 0.0 {
+            return Err(Violation {
+                kind: InvariantKind::NegativeSize,
+                message: format!("{}: {name} is negative", node_text(path, style)),
+            });
+        }
+    }
+    let display_none = style.display == Display::None;
+    if (hidden || display_none) && layout.size != Size::ZERO {
+        let reason = if display_none {
+            "is display: None"
+        } else {
+            "is in a display: None node"
+        };
+        return Err(Violation {
+            kind: InvariantKind::HiddenNotZero,
+            message: format!(
+                "{}: {reason}, but its size isn't zero",
+                node_text(path, style)
+            ),
+        });
+    }
+    for (index, child) in tree.child_ids(node).enumerate() {
+        path.push(index);
+        check_recursive(tree, child, path, hidden || display_none)?;
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Like `root > 0 > 2 (size, border)`: the third child of the first child of the root,
+/// with the style fields that differ from [`Style::DEFAULT`].
+fn node_text(path: &[usize], style: &Style) -> String {
+    let path = std::iter::once("root".to_string())
+        .chain(path.iter().map(|index| index.to_string()))
+        .collect::<Vec<_>>()
+        .join(" > ");
+    let fields: Vec<&str> = StyleField::ALL
+        .iter()
+        .filter(|field| !field.is_equal(style, &Style::DEFAULT))
+        .map(|field| field.name())
+        .collect();
+    if fields.is_empty() {
+        path
+    } else {
+        format!("{path} ({})", fields.join(", "))
+    }
+}
+
+/// The values of the layout, with their names.
+fn layout_values(layout: &Layout) -> [(&'static str, f32); 20] {
+    [
+        ("location.x", layout.location.x),
+        ("location.y", layout.location.y),
+        ("size.width", layout.size.width),
+        ("size.height", layout.size.height),
+        ("content_size.width", layout.content_size.width),
+        ("content_size.height", layout.content_size.height),
+        ("scrollbar_size.width", layout.scrollbar_size.width),
+        ("scrollbar_size.height", layout.scrollbar_size.height),
+        ("border.left", layout.border.left),
+        ("border.right", layout.border.right),
+        ("border.top", layout.border.top),
+        ("border.bottom", layout.border.bottom),
+        ("padding.left", layout.padding.left),
+        ("padding.right", layout.padding.right),
+        ("padding.top", layout.padding.top),
+        ("padding.bottom", layout.padding.bottom),
+        ("margin.left", layout.margin.left),
+        ("margin.right", layout.margin.right),
+        ("margin.top", layout.margin.top),
+        ("margin.bottom", layout.margin.bottom),
+    ]
+}
+
+/// How many smaller documents [`minimize`] checks at most, as every check lays out the whole document.
+/// The failures of large documents (like stress trees) are reported partially minimized instead of freezing the ui.
+const MAX_MINIMIZE_CHECKS: usize = 500;
+
+/// Removes nodes and resets style fields (to [`Style::DEFAULT`]) as long as the document still fails with `kind`.
+/// The default style, the classes and the style variants are dropped, as only the styles of the nodes are laid out.
+///
+/// Like delta debugging, the children of each node are removed in chunks: all of them, then halves, quarters etc..
+/// down to single children, so that a large document shrinks in a few checks. Then the whole style of each node is reset,
+/// and only then its fields one by one. Returns false as the second value if it ran out of checks before it was done.
+pub fn minimize(document: &Document, kind: InvariantKind, max_checks: usize) -> (Document, bool) {
+    fn strip_recursive(node: &mut DocumentNode) {
+        node.class = None;
+        node.variants.clear();
+        node.children.iter_mut().for_each(strip_recursive);
+    }
+    let mut checks = 0;
+    // out of checks counts as not failing, so nothing is changed anymore
+    let mut fails = |document: &Document| {
+        checks += 1;
+        checks <= max_checks && check(document).is_err_and(|violation| violation.kind == kind)
+    };
+    let mut document = document.clone();
+    document.default_style = Style::DEFAULT;
+    document.classes.clear();
+    strip_recursive(&mut document.root);
+    let mut changed = true;
+    while changed {
+        changed = false;
+        // removing the children of a node only changes the indices of the nodes after it
+        let mut node = 0;
+        while node < node_count(&document.root) {
+            let mut chunk = nth_node(&mut document.root, node).children.len();
+            while chunk > 0 {
+                let mut start = 0;
+                while start < nth_node(&mut document.root, node).children.len() {
+                    let mut smaller = document.clone();
+                    let children = &mut nth_node(&mut smaller.root, node).children;
+                    let end = (start + chunk).min(children.len());
+                    children.drain(start..end);
+                    if fails(&smaller) {
+                        document = smaller;
+                        changed = true;
+                    } else {
+                        start += chunk;
+                    }
+                }
+                chunk /= 2;
+            }
+            node += 1;
+        }
+        for node in 0..node_count(&document.root) {
+            let style = &nth_node(&mut document.root, node).style;
+            let fields: Vec<StyleField> = StyleField::ALL
+                .iter()
+                .copied()
+                .filter(|field| !field.is_equal(style, &Style::DEFAULT))
+                .collect();
+            if fields.is_empty() {
+                continue;
+            }
+            let mut smaller = document.clone();
+            nth_node(&mut smaller.root, node).style = Style::DEFAULT;
+            if fields.len() > 1 && fails(&smaller) {
+                document = smaller;
+                changed = true;
+                continue;
+            }
+            for field in fields {
+                let mut smaller = document.clone();
+                field.copy(
+                    &Style::DEFAULT,
+                    &mut nth_node(&mut smaller.root, node).style,
+                );
+                if fails(&smaller) {
+                    document = smaller;
+                    changed = true;
+                }
+            }
+        }
+    }
+    (document, checks <= max_checks)
+}
+
+fn node_count(node: &DocumentNode) -> usize {
+    1 + node.children.iter().map(node_count).sum::<usize>()
+}
+/// The node at `index` in tree order (depth first, pre-order), where the root is 0.
+fn nth_node(root: &mut DocumentNode, index: usize) -> &mut DocumentNode {
+    let mut index = index;
+    let mut node = root;
+    while index > 0 {
+        index -= 1;
+        let mut children = node.children.iter_mut();
+        node = loop {
+            let child = children.next().expect("index out of bounds");
+            let count = node_count(child);
+            if index < count {
+                break child;
+            }
+            index -= count;
+        };
+    }
+    node
+}
+/// A style with random values in all fields, including edge cases like negative, huge and zero values.
+fn random_style(rng: &mut Rng) -> Style {
+    fn number(rng: &mut Rng) -> f32 {
+        *rng.pick(&[0.0, 1.0, 10.0, 100.0, -10.0, 1e7, 0.5, 33.3])
+    }
+    fn length_percentage(rng: &mut Rng) -> LengthPercentage {
+        if rng.chance(0.5) {
+            LengthPercentage::Length(number(rng))
+        } else {
+            LengthPercentage::Percent(number(rng) / 100.0)
+        }
+    }
+    fn length_percentage_auto(rng: &mut Rng) -> LengthPercentageAuto {
+        match rng.below(3) {
+            0 => LengthPercentageAuto::Auto,
+            1 => LengthPercentageAuto::Length(number(rng)),
+            _ => LengthPercentageAuto::Percent(number(rng) / 100.0),
+        }
+    }
+    fn dimension(rng: &mut Rng) -> Dimension {
+        match rng.below(3) {
+            0 => Dimension::Auto,
+            1 => Dimension::Length(number(rng)),
+            _ => Dimension::Percent(number(rng) / 100.0),
+        }
+    }
+    fn rect<T>(rng: &mut Rng, side: fn(&mut Rng) -> T) -> Rect<T> {
+        Rect {
+            left: side(rng),
+            right: side(rng),
+            top: side(rng),
+            bottom: side(rng),
+        }
+    }
+    fn size<T>(rng: &mut Rng, axis: fn(&mut Rng) -> T) -> Size<T> {
+        Size {
+            width: axis(rng),
+            height: axis(rng),
+        }
+    }
+    /// Random css text from the pieces, parsed with the same parser as the style editor.
+    fn css<T: CssValue + Default>(rng: &mut Rng, pieces: &[&str], max_count: usize) -> T {
+        let text: Vec<&str> = (0..rng.below(max_count + 1))
+            .map(|_| *rng.pick(pieces))
+            .collect();
+        T::parse_css(&text.join(" ")).unwrap_or_default()
+    }
+    const TRACKS: &[&str] = &[
+        "100px",
+        "0px",
+        "-5px",
+        "50%",
+        "1fr",
+        "0fr",
+        "auto",
+        "min-content",
+        "max-content",
+        "fit-content(50px)",
+        "minmax(50px, 1fr)",
+        "minmax(auto, 10%)",
+    ];
+    const REPETITIONS: &[&str] = &[
+        "repeat(3, 1fr)",
+        "repeat(auto-fill, minmax(80px, 1fr))",
+        "repeat(auto-fit, 100px)",
+    ];
+    const PLACEMENTS: &[&str] = &["auto", "1", "-1", "span 2", "1 / -1", "3 / span 2", "0"];
+    let alignments = [
+        AlignItems::Start,
+        AlignItems::End,
+        AlignItems::FlexStart,
+        AlignItems::FlexEnd,
+        AlignItems::Center,
+        AlignItems::Baseline,
+        AlignItems::Stretch,
+    ];
+    let content_alignments = [
+        AlignContent::Start,
+        AlignContent::End,
+        AlignContent::FlexStart,
+        AlignContent::FlexEnd,
+        AlignContent::Center,
+        AlignContent::Stretch,
+        AlignContent::SpaceBetween,
+        AlignContent::SpaceEvenly,
+        AlignContent::SpaceAround,
+    ];
+    let overflows = [
+        Overflow::Visible,
+        Overflow::Clip,
+        Overflow::Hidden,
+        Overflow::Scroll,
+    ];
+    let mut templates: Vec<&str> = TRACKS.to_vec();
+    templates.extend(REPETITIONS);
+    Style {
+        display: *rng.pick(&[Display::Flex, Display::Grid, Display::Block, Display::None]),
+        item_is_table: rng.chance(0.1),
+        box_sizing: *rng.pick(&[BoxSizing::BorderBox, BoxSizing::ContentBox]),
+        overflow: taffy::Point {
+            x: *rng.pick(&overflows),
+            y: *rng.pick(&overflows),
+        },
+        scrollbar_width: number(rng),
+        position: *rng.pick(&[Position::Relative, Position::Absolute]),
+        inset: rect(rng, length_percentage_auto),
+        size: size(rng, dimension),
+        min_size: size(rng, dimension),
+        max_size: size(rng, dimension),
+        aspect_ratio: rng.chance(0.5).then(|| number(rng)),
+        margin: rect(rng, length_percentage_auto),
+        padding: rect(rng, length_percentage),
+        border: rect(rng, length_percentage),
+        align_items: rng.chance(0.7).then(|| *rng.pick(&alignments)),
+        align_self: rng.chance(0.7).then(|| *rng.pick(&alignments)),
+        justify_items: rng.chance(0.7).then(|| *rng.pick(&alignments)),
+        justify_self: rng.chance(0.7).then(|| *rng.pick(&alignments)),
+        align_content: rng.chance(0.7).then(|| *rng.pick(&content_alignments)),
+        justify_content: rng.chance(0.7).then(|| *rng.pick(&content_alignments)),
+        gap: size(rng, length_percentage),
+        text_align: *rng.pick(&[
+            TextAlign::Auto,
+            TextAlign::LegacyLeft,
+            TextAlign::LegacyRight,
+            TextAlign::LegacyCenter,
+        ]),
+        flex_direction: *rng.pick(&[
+            FlexDirection::Row,
+            FlexDirection::Column,
+            FlexDirection::RowReverse,
+            FlexDirection::ColumnReverse,
+        ]),
+        flex_wrap: *rng.pick(&[FlexWrap::NoWrap, FlexWrap::Wrap, FlexWrap::WrapReverse]),
+        flex_basis: dimension(rng),
+        flex_grow: number(rng),
+        flex_shrink: number(rng),
+        grid_template_rows: css(rng, &templates, 4),
+        grid_template_columns: css(rng, &templates, 4),
+        grid_auto_rows: css(rng, TRACKS, 3),
+        grid_auto_columns: css(rng, TRACKS, 3),
+        grid_auto_flow: *rng.pick(&[
+            GridAutoFlow::Row,
+            GridAutoFlow::Column,
+            GridAutoFlow::RowDense,
+            GridAutoFlow::ColumnDense,
+        ]),
+        grid_row: css(rng, PLACEMENTS, 1),
+        grid_column: css(rng, PLACEMENTS, 1),
+    }
+}
+
+/// The settings, the run button and the failures. Returns true if "run" was clicked.
+/// `load` is set to a failure that should be loaded into the editor.
+pub fn fuzz_ui(
+    ui: &mut egui::Ui,
+    settings: &mut FuzzSettings,
+    failures: &mut Vec<FuzzFailure>,
+    load: &mut Option<Document>,
+) -> bool {
+    egui::Grid::new("fuzz settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("seed");
+            ui.add(egui::DragValue::new(&mut settings.seed));
+            ui.end_row();
+            ui.label("iterations");
+            ui.add(egui::DragValue::new(&mut settings.iterations).range(1..=100_000));
+            ui.end_row();
+            ui.label("document");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut settings.use_stress_tree, false, "current");
+                ui.radio_value(&mut settings.use_stress_tree, true, "stress tree")
+                    .on_hover_text("generated with the settings of the Stress Tree Generator");
+            });
+            ui.end_row();
+        });
+    let run = ui
+        .button("run")
+        .on_hover_text("mutates random style fields and checks the layout after every mutation. The ui freezes until it's done.")
+        .clicked();
+    ui.separator();
+    if failures.is_empty() {
+        ui.label("no failures found");
+    }
+    let mut remove = None;
+    for (i, failure) in failures.iter().enumerate() {
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                ui.visuals().error_fg_color,
+                format!(
+                    "{:?}: {}",
+                    failure.violation.kind, failure.violation.message
+                ),
+            );
+            ui.weak(format!(
+                "(seed {}, iteration {}, {} nodes)",
+                failure.seed,
+                failure.iteration,
+                node_count(&failure.document.root)
+            ));
+            if failure.partially_minimized {
+                ui.weak("partially minimized").on_hover_text(format!(
+                    "minimizing stopped after {MAX_MINIMIZE_CHECKS} checks, so some nodes or fields may not matter"
+                ));
+            }
+        });
+        ui.horizontal(|ui| {
+            if ui
+                .button("load")
+                .on_hover_text("replaces the current document with the minimized repro")
+                .clicked()
+            {
+                *load = Some(failure.document.clone());
+            }
+            if ui.button("📋 copy json").clicked() {
+                ui.ctx()
+                    .copy_text(serde_json::to_string_pretty(&failure.document).unwrap_or_default());
+            }
+            if ui
+                .button("🗑")
+                .on_hover_text("forget this failure")
+                .clicked()
+            {
+                remove = Some(i);
+            }
+        });
+    }
+    if let Some(i) = remove {
+        failures.remove(i);
+    }
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style_class::ClassMembership;
+    use crate::style_variant::StyleVariant;
+
+    /// A leaf whose flex grow is `id`, to tell the nodes apart.
+    fn node(id: f32, children: Vec<DocumentNode>) -> DocumentNode {
+        DocumentNode::with_children(
+            Style {
+                flex_grow: id,
+                ..Style::DEFAULT
+            },
+            children,
+        )
+    }
+    /// 0 ( 1 ( 2 3 ) 4 ( 5 ) ), numbered in tree order.
+    fn numbered_tree() -> DocumentNode {
+        node(
+            0.0,
+            vec![
+                node(1.0, vec![node(2.0, vec![]), node(3.0, vec![])]),
+                node(4.0, vec![node(5.0, vec![])]),
+            ],
+        )
+    }
+    fn ids(node: &DocumentNode) -> Vec<f32> {
+        std::iter::once(node.style.flex_grow)
+            .chain(node.children.iter().flat_map(ids))
+            .collect()
+    }
+    fn document(root: DocumentNode) -> Document {
+        Document {
+            default_style: Style::DEFAULT,
+            classes: Default::default(),
+            root,
+        }
+    }
+
+    #[test]
+    fn nth_node_is_in_tree_order() {
+        let mut root = numbered_tree();
+        assert_eq!(node_count(&root), 6);
+        for index in 0..6 {
+            assert_eq!(nth_node(&mut root, index).style.flex_grow, index as f32);
+        }
+    }
+
+    #[test]
+    fn minimize_keeps_only_what_fails() {
+        // a negative percentage border on the root makes its width negative
+        let mut root = numbered_tree();
+        root.style.border.right = LengthPercentage::Percent(-0.1);
+        root.style.padding.left = LengthPercentage::Length(5.0);
+        root.children[0].class = Some(ClassMembership {
+            class: "card".to_string(),
+            overrides: Default::default(),
+        });
+        root.children[1].variants.push(StyleVariant {
+            min_width: Some(600.0),
+            max_width: None,
+            overrides: Default::default(),
+            style: Style::DEFAULT,
+        });
+        let mut document = document(root);
+        document.classes.insert("card".to_string(), Style::DEFAULT);
+        let violation = check(&document).unwrap_err();
+        assert_eq!(violation.kind, InvariantKind::NegativeSize);
+
+        let (minimized, complete) = minimize(&document, violation.kind, MAX_MINIMIZE_CHECKS);
+        assert!(complete);
+        assert_eq!(node_count(&minimized.root), 1);
+        let fields: Vec<StyleField> = StyleField::ALL
+            .iter()
+            .copied()
+            .filter(|field| !field.is_equal(&minimized.root.style, &Style::DEFAULT))
+            .collect();
+        assert_eq!(fields, [StyleField::Border]);
+        assert!(minimized.classes.is_empty());
+        assert!(minimized.root.class.is_none() && minimized.root.variants.is_empty());
+        assert_eq!(
+            check(&minimized).unwrap_err().message,
+            "root (border): size.width is negative"
+        );
+    }
+
+    #[test]
+    fn minimize_removes_many_children_in_few_checks() {
+        let mut root = node(
+            0.0,
+            (1..=1000)
+                .map(|id| node(id as f32, vec![node(0.0, vec![])]))
+                .collect(),
+        );
+        root.style.border.right = LengthPercentage::Percent(-0.1);
+        let document = document(root);
+        // removing the children one by one would take a thousand checks
+        let (minimized, complete) = minimize(&document, InvariantKind::NegativeSize, 10);
+        assert!(complete);
+        assert_eq!(node_count(&minimized.root), 1);
+
+        let (partial, complete) = minimize(&document, InvariantKind::NegativeSize, 0);
+        assert!(!complete);
+        assert_eq!(ids(&partial.root), ids(&document.root));
+    }
+
+    #[test]
+    fn fuzz_with_a_fixed_seed() {
+        let document = document(numbered_tree());
+        let settings = FuzzSettings {
+            seed: 4,
+            iterations: 300,
+            use_stress_tree: false,
+        };
+        let failures = fuzz(&document, &settings);
+        assert!(!failures.is_empty());
+        for failure in &failures {
+            let violation = check(&failure.document).unwrap_err();
+            assert_eq!(violation.kind, failure.violation.kind);
+            assert_eq!(violation.message, failure.violation.message);
+        }
+        // the same seed finds the same failures
+        let messages = |failures: &[FuzzFailure]| -> Vec<String> {
+            failures
+                .iter()
+                .map(|failure| failure.violation.message.clone())
+                .collect()
+        };
+        assert_eq!(messages(&fuzz(&document, &settings)), messages(&failures));
+    }
+}
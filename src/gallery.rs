@@ -0,0 +1,369 @@
+//! A curated set of example documents, to showcase how taffy lays out common patterns.
+use taffy::{
+    prelude::{
+        auto, fr, length, line, minmax, percent, repeat, span, FromFlex, FromLength, FromPercent,
+    },
+    AlignContent, AlignItems, Display, FlexDirection, FlexWrap, GridTrackRepetition, Line,
+    Position, Rect, Size, Style,
+};
+
+use crate::document::{Document, DocumentNode};
+
+pub struct GalleryEntry {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub build: fn() -> Document,
+}
+
+pub const GALLERY: &[GalleryEntry] = &[
+    GalleryEntry {
+        name: "holy grail",
+        description: "A grid with header and footer spanning all three columns, and nav | main | aside in between. The middle row and column take the remaining space with `fr` units.",
+        build: holy_grail,
+    },
+    GalleryEntry {
+        name: "sticky footer",
+        description: "A flex column where the main content has `flex_grow: 1`, pushing the footer to the bottom of the container.",
+        build: sticky_footer,
+    },
+    GalleryEntry {
+        name: "card grid (auto-fill)",
+        description: "A grid with `repeat(auto-fill, minmax(150px, 1fr))` columns. As many columns as fit in the width are created, and the cards stretch to fill the leftover space.",
+        build: card_grid,
+    },
+    GalleryEntry {
+        name: "centered modal",
+        description: "A fixed size modal centered in its container with `justify_content: Center` and `align_items: Center`.",
+        build: centered_modal,
+    },
+    GalleryEntry {
+        name: "nav bar (space-between)",
+        description: "A flex row with `justify_content: SpaceBetween`. The logo, links and button are pushed to the start, middle and end.",
+        build: nav_bar,
+    },
+    GalleryEntry {
+        name: "masonry-like wrap",
+        description: "A flex column with `flex_wrap: Wrap` and a fixed height. Items of varying heights fill a column and then wrap into the next one.",
+        build: masonry_wrap,
+    },
+    GalleryEntry {
+        name: "absolute overlay",
+        description: "An `Absolute` overlay with all insets set to zero covers its parent, and a badge is pinned to the top right corner with `top` and `right` insets.",
+        build: absolute_overlay,
+    },
+    GalleryEntry {
+        name: "aspect-ratio media",
+        description: "Media boxes with a percentage/fixed width and `aspect_ratio`, so that the height is derived from the width.",
+        build: aspect_ratio_media,
+    },
+];
+
+// These pin the generic input of taffy's helpers to `f32`, so that we don't need to suffix every float literal.
+pub(crate) fn px<T: FromLength>(value: f32) -> T {
+    length(value)
+}
+pub(crate) fn fraction<T: FromFlex>(value: f32) -> T {
+    fr(value)
+}
+pub(crate) fn pct<T: FromPercent>(value: f32) -> T {
+    percent(value)
+}
+
+fn document(root: DocumentNode) -> Document {
+    Document {
+        default_style: base(),
+        classes: Default::default(),
+        root,
+    }
+}
+
+/// The base style of all nodes in the gallery (and the default style of the documents).
+/// A thin border, so that the nodes can be told apart.
+fn base() -> Style {
+    Style {
+        border: px(2.0),
+        ..Style::DEFAULT
+    }
+}
+fn fixed(width: f32, height: f32) -> Style {
+    Style {
+        size: Size {
+            width: px(width),
+            height: px(height),
+        },
+        ..base()
+    }
+}
+
+fn holy_grail() -> Document {
+    let area = |row: i16, column: Line<taffy::GridPlacement>| Style {
+        grid_row: Line {
+            start: line(row),
+            end: auto(),
+        },
+        grid_column: column,
+        ..base()
+    };
+    let single_column = |column: i16| Line {
+        start: line(column),
+        end: auto(),
+    };
+    let full_width = Line {
+        start: line(1),
+        end: span(3),
+    };
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Grid,
+            size: Size {
+                width: px(800.0),
+                height: px(600.0),
+            },
+            grid_template_columns: vec![px(150.0), fraction(1.0), px(150.0)],
+            grid_template_rows: vec![px(80.0), fraction(1.0), px(60.0)],
+            gap: px(10.0),
+            ..base()
+        },
+        vec![
+            DocumentNode::leaf(area(1, full_width)),
+            DocumentNode::leaf(area(2, single_column(1))),
+            DocumentNode::leaf(area(2, single_column(2))),
+            DocumentNode::leaf(area(2, single_column(3))),
+            DocumentNode::leaf(area(3, full_width)),
+        ],
+    ))
+}
+
+fn sticky_footer() -> Document {
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            size: Size {
+                width: px(600.0),
+                height: px(500.0),
+            },
+            ..base()
+        },
+        vec![
+            DocumentNode::leaf(Style {
+                size: Size {
+                    width: auto(),
+                    height: px(60.0),
+                },
+                ..base()
+            }),
+            DocumentNode::leaf(Style {
+                flex_grow: 1.0,
+                ..base()
+            }),
+            DocumentNode::leaf(Style {
+                size: Size {
+                    width: auto(),
+                    height: px(50.0),
+                },
+                ..base()
+            }),
+        ],
+    ))
+}
+
+fn card_grid() -> Document {
+    let card = || {
+        DocumentNode::leaf(Style {
+            size: Size {
+                width: auto(),
+                height: px(100.0),
+            },
+            ..base()
+        })
+    };
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Grid,
+            size: Size {
+                width: px(700.0),
+                height: auto(),
+            },
+            grid_template_columns: vec![repeat(
+                GridTrackRepetition::AutoFill,
+                vec![minmax(px(150.0), fraction(1.0))],
+            )],
+            gap: px(10.0),
+            padding: px(10.0),
+            ..base()
+        },
+        (0..7).map(|_| card()).collect(),
+    ))
+}
+
+fn centered_modal() -> Document {
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Flex,
+            justify_content: Some(AlignContent::Center),
+            align_items: Some(AlignItems::Center),
+            ..fixed(800.0, 600.0)
+        },
+        vec![DocumentNode::with_children(
+            Style {
+                display: Display::Flex,
+                flex_direction: FlexDirection::Column,
+                padding: px(20.0),
+                gap: px(10.0),
+                ..fixed(400.0, 250.0)
+            },
+            vec![
+                DocumentNode::leaf(Style {
+                    size: Size {
+                        width: auto(),
+                        height: px(30.0),
+                    },
+                    ..base()
+                }),
+                DocumentNode::leaf(Style {
+                    flex_grow: 1.0,
+                    ..base()
+                }),
+            ],
+        )],
+    ))
+}
+
+fn nav_bar() -> Document {
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Flex,
+            justify_content: Some(AlignContent::SpaceBetween),
+            align_items: Some(AlignItems::Center),
+            padding: Rect {
+                left: px(20.0),
+                right: px(20.0),
+                top: px(10.0),
+                bottom: px(10.0),
+            },
+            size: Size {
+                width: px(800.0),
+                height: auto(),
+            },
+            ..base()
+        },
+        vec![
+            DocumentNode::leaf(fixed(120.0, 40.0)),
+            DocumentNode::with_children(
+                Style {
+                    display: Display::Flex,
+                    gap: px(10.0),
+                    ..base()
+                },
+                (0..3)
+                    .map(|_| DocumentNode::leaf(fixed(80.0, 30.0)))
+                    .collect(),
+            ),
+            DocumentNode::leaf(fixed(100.0, 40.0)),
+        ],
+    ))
+}
+
+fn masonry_wrap() -> Document {
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            flex_wrap: FlexWrap::Wrap,
+            align_content: Some(AlignContent::Start),
+            gap: px(10.0),
+            padding: px(10.0),
+            ..fixed(800.0, 500.0)
+        },
+        [
+            120.0, 200.0, 90.0, 160.0, 140.0, 220.0, 100.0, 180.0, 130.0, 60.0,
+        ]
+        .into_iter()
+        .map(|height| DocumentNode::leaf(fixed(180.0, height)))
+        .collect(),
+    ))
+}
+
+fn absolute_overlay() -> Document {
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Flex,
+            padding: px(10.0),
+            ..fixed(600.0, 400.0)
+        },
+        vec![
+            DocumentNode::leaf(Style {
+                flex_grow: 1.0,
+                ..base()
+            }),
+            DocumentNode::leaf(Style {
+                position: Position::Absolute,
+                inset: px(0.0),
+                ..base()
+            }),
+            DocumentNode::leaf(Style {
+                position: Position::Absolute,
+                inset: Rect {
+                    left: auto(),
+                    right: px(10.0),
+                    top: px(10.0),
+                    bottom: auto(),
+                },
+                ..fixed(40.0, 40.0)
+            }),
+        ],
+    ))
+}
+
+fn aspect_ratio_media() -> Document {
+    document(DocumentNode::with_children(
+        Style {
+            display: Display::Flex,
+            flex_direction: FlexDirection::Column,
+            gap: px(10.0),
+            padding: px(10.0),
+            size: Size {
+                width: px(600.0),
+                height: auto(),
+            },
+            ..base()
+        },
+        vec![
+            DocumentNode::leaf(Style {
+                size: Size {
+                    width: pct(1.0),
+                    height: auto(),
+                },
+                aspect_ratio: Some(16.0 / 9.0),
+                ..base()
+            }),
+            DocumentNode::leaf(Style {
+                size: Size {
+                    width: auto(),
+                    height: px(40.0),
+                },
+                ..base()
+            }),
+            DocumentNode::with_children(
+                Style {
+                    display: Display::Flex,
+                    gap: px(10.0),
+                    ..base()
+                },
+                (0..4)
+                    .map(|_| {
+                        DocumentNode::leaf(Style {
+                            size: Size {
+                                width: px(100.0),
+                                height: auto(),
+                            },
+                            aspect_ratio: Some(1.0),
+                            ..base()
+                        })
+                    })
+                    .collect(),
+            ),
+        ],
+    ))
+}
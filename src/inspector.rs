@@ -0,0 +1,253 @@
+use std::collections::HashMap;
+
+use egui::{Align2, Color32, FontId, Painter, Sense, Stroke};
+use taffy::{Layout, NodeId, PrintTree, TaffyTree};
+
+use crate::paint_settings::Palette;
+
+/// Shows the final layout of the selected node as a box model diagram + a table of values.
+/// With the `unrounded` layouts of the node and its ancestors, the values before rounding are shown next to the rounded ones.
+pub fn layout_inspector_ui(
+    ui: &mut egui::Ui,
+    tree: &TaffyTree,
+    node_id: NodeId,
+    palette: &Palette,
+    unrounded: Option<&HashMap<NodeId, Layout>>,
+) {
+    let layout = *tree.get_final_layout(node_id);
+    let absolute = absolute_location(tree, node_id);
+    let unrounded = unrounded.and_then(|layouts| {
+        let layout = layouts.get(&node_id)?;
+        // the sum of the unrounded locations, like `absolute_location`
+        let mut absolute = layout.location;
+        let mut current = node_id;
+        while let Some(parent) = tree.parent(current) {
+            let parent_location = layouts.get(&parent)?.location;
+            absolute.x += parent_location.x;
+            absolute.y += parent_location.y;
+            current = parent;
+        }
+        Some((layout, absolute))
+    });
+    ui.horizontal(|ui| {
+        ui.strong(format!("{:?}", node_id));
+        if ui
+            .button("📋 copy")
+            .on_hover_text("copies the layout values of the selected node to the clipboard")
+            .clicked()
+        {
+            ui.ctx()
+                .copy_text(layout_values_text(node_id, &layout, absolute));
+        }
+    });
+    ui.separator();
+    box_model_ui(ui, &layout, palette);
+    ui.separator();
+    let values = |layout: &Layout, absolute: taffy::Point<f32>| {
+        [
+            ("location", layout.location.x, layout.location.y),
+            ("absolute location", absolute.x, absolute.y),
+            ("size", layout.size.width, layout.size.height),
+            (
+                "content_size",
+                layout.content_size.width,
+                layout.content_size.height,
+            ),
+            (
+                "scrollbar_size",
+                layout.scrollbar_size.width,
+                layout.scrollbar_size.height,
+            ),
+        ]
+    };
+    let unrounded_values = unrounded.map(|(layout, absolute)| values(layout, absolute));
+    egui::Grid::new("layout inspector values")
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label("x / width");
+            ui.label("y / height");
+            if unrounded_values.is_some() {
+                ui.label("unrounded").on_hover_text(
+                    "the values before taffy rounded them to whole pixels. differences of more than half a pixel are highlighted",
+                );
+                ui.label("");
+            }
+            ui.end_row();
+            for (index, (name, x, y)) in values(&layout, absolute).into_iter().enumerate() {
+                ui.label(name);
+                ui.label(x.to_string());
+                ui.label(y.to_string());
+                if let Some(unrounded_values) = &unrounded_values {
+                    let (_, unrounded_x, unrounded_y) = unrounded_values[index];
+                    for (rounded, unrounded) in [(x, unrounded_x), (y, unrounded_y)] {
+                        let text = egui::RichText::new(unrounded.to_string());
+                        if (rounded - unrounded).abs() > 0.5 {
+                            ui.label(text.color(ui.visuals().warn_fg_color));
+                        } else {
+                            ui.label(text.weak());
+                        }
+                    }
+                }
+                ui.end_row();
+            }
+        });
+    egui::Grid::new("layout inspector edges")
+        .num_columns(5)
+        .striped(true)
+        .show(ui, |ui| {
+            ui.label("");
+            ui.label("left");
+            ui.label("right");
+            ui.label("top");
+            ui.label("bottom");
+            ui.end_row();
+            for (name, rect) in [
+                ("margin", layout.margin),
+                ("border", layout.border),
+                ("padding", layout.padding),
+            ] {
+                ui.label(name);
+                ui.label(rect.left.to_string());
+                ui.label(rect.right.to_string());
+                ui.label(rect.top.to_string());
+                ui.label(rect.bottom.to_string());
+                ui.end_row();
+            }
+        });
+    ui.label(format!("order: {}", layout.order));
+}
+
+/// Sums up the locations of the node and all of its ancestors.
+/// Taffy's locations are relative to the parent, so this gives us the position relative to the root's parent (page coordinates).
+pub fn absolute_location(tree: &TaffyTree, node_id: NodeId) -> taffy::Point<f32> {
+    let mut location = tree.get_final_layout(node_id).location;
+    let mut current = node_id;
+    while let Some(parent) = tree.parent(current) {
+        let parent_location = tree.get_final_layout(parent).location;
+        location.x += parent_location.x;
+        location.y += parent_location.y;
+        current = parent;
+    }
+    location
+}
+
+/// The text that is copied to clipboard by the copy button.
+fn layout_values_text(node_id: NodeId, layout: &Layout, absolute: taffy::Point<f32>) -> String {
+    let rect_text = |rect: taffy::Rect<f32>| {
+        format!(
+            "left: {}, right: {}, top: {}, bottom: {}",
+            rect.left, rect.right, rect.top, rect.bottom
+        )
+    };
+    format!(
+        "node: {:?}
+location: x: {}, y: {}
+absolute location: x: {}, y: {}
+size: width: {}, height: {}
+content_size: width: {}, height: {}
+scrollbar_size: width: {}, height: {}
+margin: {}
+border: {}
+padding: {}",
+        node_id,
+        layout.location.x,
+        layout.location.y,
+        absolute.x,
+        absolute.y,
+        layout.size.width,
+        layout.size.height,
+        layout.content_size.width,
+        layout.content_size.height,
+        layout.scrollbar_size.width,
+        layout.scrollbar_size.height,
+        rect_text(layout.margin),
+        rect_text(layout.border),
+        rect_text(layout.padding),
+    )
+}
+
+/// Paints nested boxes for margin, border, padding and content (like the box model view of browser dev tools).
+/// Each box displays its edge values on the respective sides and the content box displays the content size.
+/// The space reserved for scrollbars is taken from the content box, like taffy does, and shown below the content size.
+fn box_model_ui(ui: &mut egui::Ui, layout: &Layout, palette: &Palette) {
+    /// The gap between the nested boxes, where we write the edge values.
+    const RING: f32 = 28.0;
+    let desired_size = egui::vec2(ui.available_width().max(8.0 * RING), 7.0 * RING);
+    let (rect, _) = ui.allocate_exact_size(desired_size, Sense::hover());
+    let painter = ui.painter_at(rect);
+    let content_width = layout.size.width
+        - layout.border.left
+        - layout.border.right
+        - layout.padding.left
+        - layout.padding.right
+        - layout.scrollbar_size.width;
+    let content_height = layout.size.height
+        - layout.border.top
+        - layout.border.bottom
+        - layout.padding.top
+        - layout.padding.bottom
+        - layout.scrollbar_size.height;
+    let mut current = rect;
+    for (name, color, edges) in [
+        ("margin", palette.margin, Some(layout.margin)),
+        ("border", palette.border, Some(layout.border)),
+        ("padding", palette.padding, Some(layout.padding)),
+        ("content", palette.content, None),
+    ] {
+        painter.rect(current, 0.0, color, Stroke::new(1.0_f32, Color32::BLACK));
+        painter.text(
+            current.left_top() + egui::vec2(3.0, 2.0),
+            Align2::LEFT_TOP,
+            name,
+            FontId::proportional(10.0),
+            Color32::BLACK,
+        );
+        match edges {
+            Some(edges) => {
+                let center = current.center();
+                box_model_edge_text(
+                    &painter,
+                    egui::pos2(current.left() + RING / 2.0, center.y),
+                    edges.left,
+                );
+                box_model_edge_text(
+                    &painter,
+                    egui::pos2(current.right() - RING / 2.0, center.y),
+                    edges.right,
+                );
+                box_model_edge_text(
+                    &painter,
+                    egui::pos2(center.x, current.top() + RING / 2.0),
+                    edges.top,
+                );
+                box_model_edge_text(
+                    &painter,
+                    egui::pos2(center.x, current.bottom() - RING / 2.0),
+                    edges.bottom,
+                );
+            }
+            None => {
+                let mut text = format!("{} × {}", content_width, content_height);
+                if layout.scrollbar_size.width > 0.0 || layout.scrollbar_size.height > 0.0 {
+                    text += &format!(
+                        "\n+ scrollbar {} × {}",
+                        layout.scrollbar_size.width, layout.scrollbar_size.height
+                    );
+                }
+                box_model_edge_text(&painter, current.center(), text);
+            }
+        }
+        current = current.shrink(RING);
+    }
+}
+fn box_model_edge_text(painter: &Painter, pos: egui::Pos2, value: impl ToString) {
+    painter.text(
+        pos,
+        Align2::CENTER_CENTER,
+        value.to_string(),
+        FontId::monospace(11.0),
+        Color32::BLACK,
+    );
+}
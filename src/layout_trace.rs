@@ -0,0 +1,815 @@
+//! Records every `compute_child_layout` call of a layout pass, to explain how the size of a node was derived
+//! and to see how well the layout cache of each node works.
+//!
+//! [`TracingTree`] runs the same layout algorithms as [`TaffyTree`] on its styles and children,
+//! but with its own caches. They start empty for a full layout pass, or mirror the caches of the tree
+//! (taffy doesn't expose them) to trace the same incremental pass as the tree's own layout.
+use std::collections::{HashMap, HashSet};
+
+use taffy::{
+    compute_block_layout, compute_cached_layout, compute_flexbox_layout, compute_grid_layout,
+    compute_hidden_layout, compute_leaf_layout, compute_root_layout, AvailableSpace, Cache,
+    Dimension, Display, FlexDirection, Layout, LayoutBlockContainer, LayoutFlexboxContainer,
+    LayoutGridContainer, LayoutInput, LayoutOutput, LayoutPartialTree, LengthPercentage, NodeId,
+    RequestedAxis, RunMode, Size, SizingMode, Style, TaffyTree, TraversePartialTree,
+};
+use web_time::{Duration, Instant};
+
+/// A call of `compute_child_layout`, made by a layout algorithm (or the root layout) to size or lay out a node.
+#[derive(Debug, Clone)]
+pub struct LayoutCall {
+    pub node: NodeId,
+    /// The index of the call that made this call. `None` for the call made by the root layout.
+    pub caller: Option<usize>,
+    pub inputs: LayoutInput,
+    pub output: LayoutOutput,
+    /// The result came from the cache of the node, so its children weren't visited.
+    pub cache_hit: bool,
+    /// The time spent in the call, including the calls it made.
+    pub duration: Duration,
+}
+
+/// All the calls of a layout pass, in the order they were made.
+#[derive(Debug, Default)]
+pub struct LayoutTrace {
+    pub calls: Vec<LayoutCall>,
+    /// The unrounded layouts of the nodes, as set by the layout algorithms.
+    pub layouts: HashMap<NodeId, Layout>,
+}
+
+impl LayoutTrace {
+    /// Lays out the tree starting at `root` (like [`TaffyTree::compute_layout`]) and records the calls.
+    pub fn record(tree: &TaffyTree, root: NodeId, available_space: Size<AvailableSpace>) -> Self {
+        Self::record_with_caches(tree, root, available_space, &mut HashMap::new())
+    }
+    /// Records the incremental layout pass that `tree.compute_layout` is about to run, using `caches` as the
+    /// mirror of the caches of the tree. Must be called before every layout of the tree, while it's still dirty,
+    /// as the caches that taffy cleared are found from the dirty nodes.
+    pub fn record_incremental(
+        tree: &TaffyTree,
+        root: NodeId,
+        available_space: Size<AvailableSpace>,
+        caches: &mut HashMap<NodeId, Cache>,
+    ) -> Self {
+        let mut live = HashSet::new();
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            live.insert(node);
+            stack.extend(tree.child_ids(node));
+        }
+        caches.retain(|node, _| live.contains(node) && !tree.dirty(*node).unwrap_or(true));
+        Self::record_with_caches(tree, root, available_space, caches)
+    }
+    fn record_with_caches(
+        tree: &TaffyTree,
+        root: NodeId,
+        available_space: Size<AvailableSpace>,
+        caches: &mut HashMap<NodeId, Cache>,
+    ) -> Self {
+        let mut tracing = TracingTree {
+            tree,
+            caches,
+            trace: LayoutTrace::default(),
+            stack: vec![],
+        };
+        compute_root_layout(&mut tracing, root, available_space);
+        tracing.trace
+    }
+    /// The calls made on `node`, with their indices.
+    pub fn calls_on(&self, node: NodeId) -> impl Iterator<Item = (usize, &LayoutCall)> {
+        self.calls
+            .iter()
+            .enumerate()
+            .filter(move |(_, call)| call.node == node)
+    }
+    /// The calls made while computing the call at `index` (i.e. the calls on its children).
+    pub fn calls_made_by(&self, index: usize) -> impl Iterator<Item = (usize, &LayoutCall)> {
+        self.calls
+            .iter()
+            .enumerate()
+            .filter(move |(_, call)| call.caller == Some(index))
+    }
+}
+
+/// How often a node was asked for its layout during a layout pass, and how often the cache had the answer.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NodeCacheStats {
+    pub calls: usize,
+    pub cache_hits: usize,
+    /// Calls with [`SizingMode::ContentSize`] (the size styles of the node are ignored).
+    pub content_size: usize,
+    /// Calls with [`SizingMode::InherentSize`].
+    pub inherent_size: usize,
+    /// Calls with min-content available space (on either axis).
+    pub min_content: usize,
+    /// Calls with max-content available space (on either axis).
+    pub max_content: usize,
+}
+impl NodeCacheStats {
+    /// The calls that weren't answered by the cache, so the layout of the node (and its children) was computed.
+    pub fn computed(&self) -> usize {
+        self.calls - self.cache_hits
+    }
+}
+
+impl LayoutTrace {
+    /// The cache stats of every node that was visited in the layout pass.
+    pub fn cache_stats(&self) -> HashMap<NodeId, NodeCacheStats> {
+        let mut stats: HashMap<NodeId, NodeCacheStats> = HashMap::new();
+        for call in &self.calls {
+            let node_stats = stats.entry(call.node).or_default();
+            node_stats.calls += 1;
+            node_stats.cache_hits += usize::from(call.cache_hit);
+            match call.inputs.sizing_mode {
+                SizingMode::ContentSize => node_stats.content_size += 1,
+                SizingMode::InherentSize => node_stats.inherent_size += 1,
+            }
+            let space = call.inputs.available_space;
+            let is = |kind: AvailableSpace| space.width == kind || space.height == kind;
+            node_stats.min_content += usize::from(is(AvailableSpace::MinContent));
+            node_stats.max_content += usize::from(is(AvailableSpace::MaxContent));
+        }
+        stats
+    }
+}
+
+/// A view of a [`TaffyTree`] that can be laid out by taffy's algorithms, and records the calls in a [`LayoutTrace`].
+struct TracingTree<'t> {
+    tree: &'t TaffyTree,
+    caches: &'t mut HashMap<NodeId, Cache>,
+    trace: LayoutTrace,
+    /// The indices of the calls that are being computed. The last one is the caller of the next call.
+    stack: Vec<usize>,
+}
+
+impl TracingTree<'_> {
+    fn style(&self, node_id: NodeId) -> &Style {
+        self.tree.style(node_id).unwrap()
+    }
+}
+
+impl TraversePartialTree for TracingTree<'_> {
+    type ChildIter<'a>
+        = <TaffyTree as TraversePartialTree>::ChildIter<'a>
+    where
+        Self: 'a;
+
+    fn child_ids(&self, parent_node_id: NodeId) -> Self::ChildIter<'_> {
+        self.tree.child_ids(parent_node_id)
+    }
+    fn child_count(&self, parent_node_id: NodeId) -> usize {
+        TraversePartialTree::child_count(self.tree, parent_node_id)
+    }
+    fn get_child_id(&self, parent_node_id: NodeId, child_index: usize) -> NodeId {
+        self.tree.get_child_id(parent_node_id, child_index)
+    }
+}
+
+impl LayoutPartialTree for TracingTree<'_> {
+    type CoreContainerStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+    type CacheMut<'b>
+        = &'b mut Cache
+    where
+        Self: 'b;
+
+    fn get_core_container_style(&self, node_id: NodeId) -> Self::CoreContainerStyle<'_> {
+        self.style(node_id)
+    }
+    fn set_unrounded_layout(&mut self, node_id: NodeId, layout: &Layout) {
+        self.trace.layouts.insert(node_id, *layout);
+    }
+    fn get_cache_mut(&mut self, node_id: NodeId) -> Self::CacheMut<'_> {
+        self.caches.entry(node_id).or_default()
+    }
+    /// The same as the implementation of `TaffyTree` (with a measure function that returns zero),
+    /// except that the call is recorded.
+    fn compute_child_layout(&mut self, node_id: NodeId, inputs: LayoutInput) -> LayoutOutput {
+        let index = self.trace.calls.len();
+        self.trace.calls.push(LayoutCall {
+            node: node_id,
+            caller: self.stack.last().copied(),
+            inputs,
+            output: LayoutOutput::HIDDEN,
+            cache_hit: true,
+            duration: Duration::ZERO,
+        });
+        let start = Instant::now();
+        self.stack.push(index);
+        let output = if inputs.run_mode == RunMode::PerformHiddenLayout {
+            self.trace.calls[index].cache_hit = false;
+            compute_hidden_layout(self, node_id)
+        } else {
+            compute_cached_layout(self, node_id, inputs, |tree, node, inputs| {
+                tree.trace.calls[index].cache_hit = false;
+                let display_mode = tree.style(node).display;
+                let has_children = TraversePartialTree::child_count(tree, node) > 0;
+                match (display_mode, has_children) {
+                    (Display::None, _) => compute_hidden_layout(tree, node),
+                    (Display::Block, true) => compute_block_layout(tree, node, inputs),
+                    (Display::Flex, true) => compute_flexbox_layout(tree, node, inputs),
+                    (Display::Grid, true) => compute_grid_layout(tree, node, inputs),
+                    (_, false) => compute_leaf_layout(inputs, tree.style(node), |_, _| Size::ZERO),
+                }
+            })
+        };
+        self.stack.pop();
+        self.trace.calls[index].output = output;
+        self.trace.calls[index].duration = start.elapsed();
+        output
+    }
+}
+
+impl LayoutBlockContainer for TracingTree<'_> {
+    type BlockContainerStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+    type BlockItemStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+
+    fn get_block_container_style(&self, node_id: NodeId) -> Self::BlockContainerStyle<'_> {
+        self.style(node_id)
+    }
+    fn get_block_child_style(&self, child_node_id: NodeId) -> Self::BlockItemStyle<'_> {
+        self.style(child_node_id)
+    }
+}
+
+impl LayoutFlexboxContainer for TracingTree<'_> {
+    type FlexboxContainerStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+    type FlexboxItemStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+
+    fn get_flexbox_container_style(&self, node_id: NodeId) -> Self::FlexboxContainerStyle<'_> {
+        self.style(node_id)
+    }
+    fn get_flexbox_child_style(&self, child_node_id: NodeId) -> Self::FlexboxItemStyle<'_> {
+        self.style(child_node_id)
+    }
+}
+
+impl LayoutGridContainer for TracingTree<'_> {
+    type GridContainerStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+    type GridItemStyle<'a>
+        = &'a Style
+    where
+        Self: 'a;
+
+    fn get_grid_container_style(&self, node_id: NodeId) -> Self::GridContainerStyle<'_> {
+        self.style(node_id)
+    }
+    fn get_grid_child_style(&self, child_node_id: NodeId) -> Self::GridItemStyle<'_> {
+        self.style(child_node_id)
+    }
+}
+
+/// Rounds to 2 decimals and drops the trailing zeros.
+fn number_text(value: f32) -> String {
+    format!("{}", (value * 100.0).round() / 100.0)
+}
+fn optional_size_text(size: Size<Option<f32>>) -> String {
+    let axis = |value: Option<f32>| value.map(number_text).unwrap_or("?".to_string());
+    format!("{} × {}", axis(size.width), axis(size.height))
+}
+fn available_space_text(space: Size<AvailableSpace>) -> String {
+    let axis = |value: AvailableSpace| match value {
+        AvailableSpace::Definite(value) => number_text(value),
+        AvailableSpace::MinContent => "min-content".to_string(),
+        AvailableSpace::MaxContent => "max-content".to_string(),
+    };
+    format!("{} × {}", axis(space.width), axis(space.height))
+}
+
+/// A one line description of what the call asks for, and its result.
+pub fn call_text(call: &LayoutCall) -> String {
+    let inputs = &call.inputs;
+    let request = match inputs.run_mode {
+        RunMode::PerformHiddenLayout => {
+            return "hidden layout: an ancestor (or the node) is display: None, so it's zero sized"
+                .to_string();
+        }
+        RunMode::PerformLayout => format!(
+            "final layout with the size {}",
+            optional_size_text(inputs.known_dimensions)
+        ),
+        RunMode::ComputeSize => {
+            let axis = match inputs.axis {
+                RequestedAxis::Horizontal => "width",
+                RequestedAxis::Vertical => "height",
+                RequestedAxis::Both => "size",
+            };
+            let mut text = format!("measure {axis}");
+            if inputs.known_dimensions != Size::NONE {
+                text += &format!(" given {}", optional_size_text(inputs.known_dimensions));
+            }
+            if inputs.sizing_mode == SizingMode::ContentSize {
+                text += " (content only, ignoring its size styles)";
+            }
+            text
+        }
+    };
+    let size = call.output.size;
+    let mut text = format!(
+        "{request}, in available space {} → {} × {}",
+        available_space_text(inputs.available_space),
+        number_text(size.width),
+        number_text(size.height)
+    );
+    if call.cache_hit {
+        text += " (cached)";
+    }
+    text
+}
+
+/// The steps of the flex algorithm for a flex item, derived from its style and the calls on it in the trace.
+fn flex_item_steps(tree: &TaffyTree, node: NodeId, trace: &LayoutTrace) -> Option<Vec<String>> {
+    let parent = tree.parent(node)?;
+    let parent_style = tree.style(parent).ok()?;
+    let style = tree.style(node).ok()?;
+    if parent_style.display != Display::Flex || style.position == taffy::Position::Absolute {
+        return None;
+    }
+    let is_row = matches!(
+        parent_style.flex_direction,
+        FlexDirection::Row | FlexDirection::RowReverse
+    );
+    let axis = if is_row { "width" } else { "height" };
+    let main = |size: Size<f32>| if is_row { size.width } else { size.height };
+    let main_dimension = |size: Size<Dimension>| if is_row { size.width } else { size.height };
+    let main_available = |space: Size<AvailableSpace>| {
+        if is_row {
+            space.width
+        } else {
+            space.height
+        }
+    };
+    // the last full layout of the parent is the one whose results are in the final layout
+    let (parent_call, _) = trace
+        .calls_on(parent)
+        .filter(|(_, call)| call.inputs.run_mode == RunMode::PerformLayout && !call.cache_hit)
+        .last()?;
+    let parent_layout = trace.layouts.get(&parent)?;
+    let parent_inner = main(parent_layout.size)
+        - if is_row {
+            parent_layout.padding.left
+                + parent_layout.padding.right
+                + parent_layout.border.left
+                + parent_layout.border.right
+        } else {
+            parent_layout.padding.top
+                + parent_layout.padding.bottom
+                + parent_layout.border.top
+                + parent_layout.border.bottom
+        };
+    let layout = trace.layouts.get(&node)?;
+    let FlexItemBase {
+        calls,
+        padding_border,
+        margin,
+        box_sizing_adjustment,
+        base_size,
+    } = flex_item_base(trace, parent_call, node, style, is_row, parent_inner)?;
+    let resolve = |value: Dimension| resolve_dimension(value, parent_inner, box_sizing_adjustment);
+
+    let mut steps = vec![format!(
+        "the parent is a flex {}, so the main axis is {axis}",
+        if is_row { "row" } else { "column" }
+    )];
+    if box_sizing_adjustment > 0.0 {
+        steps.push(format!(
+            "box_sizing is content-box, so padding + border = {} is added to the sizes in the style",
+            number_text(box_sizing_adjustment)
+        ));
+    }
+    match base_size {
+        Some((basis, FlexBaseSource::Basis)) => steps.push(format!(
+            "flex base size = flex_basis = {}",
+            number_text(basis)
+        )),
+        Some((size, FlexBaseSource::Size)) => steps.push(format!(
+            "flex_basis is auto, so flex base size = {axis} = {}",
+            number_text(size)
+        )),
+        Some((content_size, FlexBaseSource::Content)) => steps.push(format!(
+            "flex_basis and {axis} are auto, so the content was measured: flex base size = {}",
+            number_text(content_size)
+        )),
+        None => {}
+    }
+    // the border box can't be smaller than the padding and border
+    let base_size = base_size.map(|(base, _)| {
+        if base < padding_border {
+            steps.push(format!(
+                "flex base size is floored by padding + border = {}",
+                number_text(padding_border)
+            ));
+        }
+        base.max(padding_border)
+    });
+    let min = resolve(main_dimension(style.min_size));
+    let max = resolve(main_dimension(style.max_size));
+    let automatic_min = calls
+        .iter()
+        .find(|call| {
+            matches!(
+                main_available(call.inputs.available_space),
+                AvailableSpace::MinContent
+            )
+        })
+        .map(|call| main(call.output.size));
+    match (min, automatic_min) {
+        (Some(min), _) => steps.push(format!("min {axis} = {}", number_text(min))),
+        (None, Some(automatic_min)) => steps.push(format!(
+            "min {axis} is auto, so the minimum is the min-content size = {}",
+            number_text(automatic_min)
+        )),
+        (None, None) => {}
+    }
+    if let Some(max) = max {
+        steps.push(format!("max {axis} = {}", number_text(max)));
+    }
+    let lower = min.or(automatic_min).unwrap_or(0.0).max(padding_border);
+    let hypothetical = base_size.map(|base| {
+        let clamped = max.map_or(base, |max| base.min(max)).max(lower);
+        steps.push(format!(
+            "hypothetical main size = flex base size clamped by min/max = {}",
+            number_text(clamped)
+        ));
+        if margin != 0.0 {
+            steps.push(format!(
+                "with the margins ({}), it takes up {} of the line when the free space is computed",
+                number_text(margin),
+                number_text(clamped + margin)
+            ));
+        }
+        clamped
+    });
+    let final_size = main(layout.size);
+    if let Some(hypothetical) = hypothetical {
+        let delta = final_size - hypothetical;
+        let siblings: Vec<(NodeId, &Style)> = tree
+            .children(parent)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|sibling| Some((sibling, tree.style(sibling).ok()?)))
+            .filter(|(_, sibling)| {
+                sibling.display != Display::None && sibling.position != taffy::Position::Absolute
+            })
+            .collect();
+        if delta > 0.01 {
+            let total: f32 = siblings.iter().map(|(_, sibling)| sibling.flex_grow).sum();
+            steps.push(format!(
+                "grew by {}: there was free space, and it takes flex_grow {} / {} (sum of the siblings) of it",
+                number_text(delta),
+                number_text(style.flex_grow),
+                number_text(total)
+            ));
+        } else if delta < -0.01 {
+            // the negative free space is shared by flex_shrink × inner flex base size (without padding and border)
+            let scaled_shrink = |style: &Style, item: &FlexItemBase<'_>| {
+                let base = item.base_size.map_or(0.0, |(base, _)| base);
+                style.flex_shrink * (base.max(item.padding_border) - item.padding_border)
+            };
+            let own = base_size.map_or(0.0, |base| style.flex_shrink * (base - padding_border));
+            let total: Option<f32> = siblings
+                .iter()
+                .map(|&(sibling, sibling_style)| {
+                    let base = flex_item_base(
+                        trace,
+                        parent_call,
+                        sibling,
+                        sibling_style,
+                        is_row,
+                        parent_inner,
+                    )?;
+                    Some(scaled_shrink(sibling_style, &base))
+                })
+                .sum();
+            let share = match total {
+                Some(total) if total > 0.0 => format!(
+                    ", which is {} / {} (sum of the siblings) of the overflow",
+                    number_text(own),
+                    number_text(total)
+                ),
+                _ => String::new(),
+            };
+            steps.push(format!(
+                "shrank by {}: the items overflowed, and it shrinks by flex_shrink {} × inner flex base size {} = {}{share}",
+                number_text(-delta),
+                number_text(style.flex_shrink),
+                number_text(base_size.map_or(0.0, |base| base - padding_border)),
+                number_text(own),
+            ));
+        } else {
+            steps.push("neither grew nor shrank".to_string());
+        }
+        if parent_style.flex_wrap != taffy::FlexWrap::NoWrap {
+            steps.push(
+                "the parent wraps, so only the siblings on the same line share the free space"
+                    .to_string(),
+            );
+        }
+    }
+    let flexed = base_size.map_or(true, |base| (final_size - base).abs() >= 0.01);
+    let clamp_note = if !flexed {
+        ""
+    } else if max.is_some_and(|max| (final_size - max).abs() < 0.01) {
+        " (clamped by max)"
+    } else if (final_size - lower).abs() < 0.01 && lower > 0.0 {
+        " (clamped by min)"
+    } else {
+        ""
+    };
+    steps.push(format!(
+        "final main size = {}{clamp_note}",
+        number_text(final_size)
+    ));
+    Some(steps)
+}
+
+/// Where the flex base size of an item comes from.
+#[derive(Debug, Clone, Copy)]
+enum FlexBaseSource {
+    Basis,
+    Size,
+    /// Measured from the content, as the flex basis and the size are auto.
+    Content,
+}
+
+/// What the flex algorithm starts from for an item, along the main axis.
+struct FlexItemBase<'t> {
+    /// The calls the parent made on the item in the traced layout.
+    calls: Vec<&'t LayoutCall>,
+    padding_border: f32,
+    margin: f32,
+    /// The padding + border that content-box sizing adds to the sizes in the style.
+    box_sizing_adjustment: f32,
+    /// Not floored by the padding and border yet.
+    base_size: Option<(f32, FlexBaseSource)>,
+}
+
+/// The flex base size of `node`, a flex item laid out by the parent call `parent_call`.
+fn flex_item_base<'t>(
+    trace: &'t LayoutTrace,
+    parent_call: usize,
+    node: NodeId,
+    style: &Style,
+    is_row: bool,
+    parent_inner: f32,
+) -> Option<FlexItemBase<'t>> {
+    let calls: Vec<&LayoutCall> = trace
+        .calls_made_by(parent_call)
+        .filter(|(_, call)| call.node == node)
+        .map(|(_, call)| call)
+        .collect();
+    let layout = trace.layouts.get(&node)?;
+    let (padding_border, margin) = if is_row {
+        (
+            layout.padding.left + layout.padding.right + layout.border.left + layout.border.right,
+            layout.margin.left + layout.margin.right,
+        )
+    } else {
+        (
+            layout.padding.top + layout.padding.bottom + layout.border.top + layout.border.bottom,
+            layout.margin.top + layout.margin.bottom,
+        )
+    };
+    // with content-box sizing, the sizes in the style are of the content box, and the padding and border are added
+    let box_sizing_adjustment = if style.box_sizing == taffy::BoxSizing::ContentBox {
+        padding_border
+    } else {
+        0.0
+    };
+    let main = |size: Size<f32>| if is_row { size.width } else { size.height };
+    let main_option = |size: Size<Option<f32>>| if is_row { size.width } else { size.height };
+    let main_available = |space: Size<AvailableSpace>| {
+        if is_row {
+            space.width
+        } else {
+            space.height
+        }
+    };
+    let resolve = |value: Dimension| resolve_dimension(value, parent_inner, box_sizing_adjustment);
+    let base_size = if let Some(basis) = resolve(style.flex_basis) {
+        Some((basis, FlexBaseSource::Basis))
+    } else if let Some(size) = resolve(if is_row {
+        style.size.width
+    } else {
+        style.size.height
+    }) {
+        Some((size, FlexBaseSource::Size))
+    } else {
+        // the measurement of the content along the main axis, with no main size given
+        calls
+            .iter()
+            .find(|call| {
+                call.inputs.run_mode == RunMode::ComputeSize
+                    && main_option(call.inputs.known_dimensions).is_none()
+                    && !matches!(
+                        main_available(call.inputs.available_space),
+                        AvailableSpace::MinContent
+                    )
+            })
+            .map(|call| (main(call.output.size), FlexBaseSource::Content))
+    };
+    Some(FlexItemBase {
+        calls,
+        padding_border,
+        margin,
+        box_sizing_adjustment,
+        base_size,
+    })
+}
+
+/// Resolves a size of the style against the inner size of the parent, adding the padding and border of content-box sizing.
+fn resolve_dimension(
+    value: Dimension,
+    parent_inner: f32,
+    box_sizing_adjustment: f32,
+) -> Option<f32> {
+    match value {
+        Dimension::Length(value) => Some(value),
+        Dimension::Percent(value) => Some(value * parent_inner),
+        Dimension::Auto => None,
+    }
+    .map(|value| value + box_sizing_adjustment)
+}
+
+/// Explains how the size of `node` was derived, as a list of the layout calls on it.
+/// Each call can be expanded to see the calls it made on the children of the node.
+pub fn explain_size_ui(ui: &mut egui::Ui, tree: &TaffyTree, node: NodeId, trace: &LayoutTrace) {
+    ui.heading(format!("{node:?}"));
+    if let Some(layout) = trace.layouts.get(&node) {
+        ui.label(format!(
+            "unrounded size: {} × {}",
+            number_text(layout.size.width),
+            number_text(layout.size.height)
+        ));
+    }
+    if let Ok(style) = tree.style(node) {
+        let dimension = |value: Dimension| match value {
+            Dimension::Length(value) => number_text(value),
+            Dimension::Percent(value) => format!("{}%", number_text(value * 100.0)),
+            Dimension::Auto => "auto".to_string(),
+        };
+        ui.label(format!(
+            "size: {} × {}, min: {} × {}, max: {} × {}",
+            dimension(style.size.width),
+            dimension(style.size.height),
+            dimension(style.min_size.width),
+            dimension(style.min_size.height),
+            dimension(style.max_size.width),
+            dimension(style.max_size.height),
+        ));
+        let padding_border = |value: LengthPercentage| match value {
+            LengthPercentage::Length(value) => value,
+            LengthPercentage::Percent(_) => 0.0,
+        };
+        if style.box_sizing == taffy::BoxSizing::ContentBox
+            && [style.padding, style.border]
+                .iter()
+                .any(|rect| padding_border(rect.left) != 0.0 || padding_border(rect.top) != 0.0)
+        {
+            ui.label("box_sizing is ContentBox, so padding and border are added to the size");
+        }
+    }
+    if let Some(steps) = flex_item_steps(tree, node, trace) {
+        ui.separator();
+        ui.strong("flex item");
+        for (i, step) in steps.iter().enumerate() {
+            ui.label(format!("{}. {step}", i + 1));
+        }
+    }
+    ui.separator();
+    let calls: Vec<(usize, &LayoutCall)> = trace.calls_on(node).collect();
+    let hits = calls.iter().filter(|(_, call)| call.cache_hit).count();
+    ui.strong(format!(
+        "layout calls ({} calls, {hits} cache hits)",
+        calls.len()
+    ));
+    for (step, (index, call)) in calls.into_iter().enumerate() {
+        let caller = match call.caller {
+            Some(caller) => format!("{:?}", trace.calls[caller].node),
+            None => "root layout".to_string(),
+        };
+        let header = format!("{}. [{caller}] {}", step + 1, call_text(call));
+        let children: Vec<&LayoutCall> = trace
+            .calls_made_by(index)
+            .map(|(_, child_call)| child_call)
+            .collect();
+        if children.is_empty() {
+            ui.label(header).on_hover_text(format!(
+                "parent size: {}",
+                optional_size_text(call.inputs.parent_size)
+            ));
+        } else {
+            egui::CollapsingHeader::new(header)
+                .id_salt(index)
+                .show(ui, |ui| {
+                    ui.weak(format!(
+                        "parent size (for percentages): {}",
+                        optional_size_text(call.inputs.parent_size)
+                    ));
+                    for child_call in children {
+                        ui.label(format!("{:?}: {}", child_call.node, call_text(child_call)));
+                    }
+                });
+        }
+    }
+}
+
+/// A table of the [`NodeCacheStats`] of all nodes in tree order.
+/// Nodes that were computed more than `threshold` times are highlighted. Clicking a node sets `clicked`.
+pub fn cache_stats_ui(
+    ui: &mut egui::Ui,
+    tree: &TaffyTree,
+    root: NodeId,
+    trace: &LayoutTrace,
+    threshold: &mut usize,
+    clicked: &mut Option<NodeId>,
+) {
+    let stats = trace.cache_stats();
+    let hits = trace.calls.iter().filter(|call| call.cache_hit).count();
+    ui.label(format!(
+        "{} calls of compute_child_layout on {} nodes, {hits} cache hits ({:.0}%)",
+        trace.calls.len(),
+        stats.len(),
+        100.0 * hits as f32 / trace.calls.len().max(1) as f32
+    ));
+    ui.horizontal(|ui| {
+        ui.label("highlight nodes computed more than");
+        ui.add(egui::DragValue::new(threshold).range(1..=100));
+        ui.label("times");
+    });
+    let hot = stats
+        .values()
+        .filter(|node_stats| node_stats.computed() > *threshold)
+        .count();
+    if hot > 0 {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            format!("{hot} nodes are re-measured excessively"),
+        );
+    }
+    fn row_recursive(
+        ui: &mut egui::Ui,
+        tree: &TaffyTree,
+        node: NodeId,
+        depth: usize,
+        stats: &HashMap<NodeId, NodeCacheStats>,
+        threshold: usize,
+        clicked: &mut Option<NodeId>,
+    ) {
+        let node_stats = stats.get(&node).copied().unwrap_or_default();
+        let mut name = egui::RichText::new(format!("{}{node:?}", "  ".repeat(depth)));
+        if node_stats.computed() > threshold {
+            name = name.color(ui.visuals().warn_fg_color).strong();
+        }
+        if ui.selectable_label(false, name).clicked() {
+            *clicked = Some(node);
+        }
+        ui.label(node_stats.calls.to_string());
+        ui.label(node_stats.cache_hits.to_string());
+        ui.label(node_stats.computed().to_string());
+        ui.label(format!(
+            "{} / {}",
+            node_stats.content_size, node_stats.inherent_size
+        ));
+        ui.label(format!(
+            "{} / {}",
+            node_stats.min_content, node_stats.max_content
+        ));
+        ui.end_row();
+        for child in tree.children(node).unwrap_or_default() {
+            row_recursive(ui, tree, child, depth + 1, stats, threshold, clicked);
+        }
+    }
+    egui::Grid::new("cache stats")
+        .striped(true)
+        .show(ui, |ui| {
+            ui.strong("node");
+            ui.strong("calls").on_hover_text("calls of compute_child_layout on the node");
+            ui.strong("hits").on_hover_text("calls answered by the cache of the node");
+            ui.strong("computed")
+                .on_hover_text("calls that ran the layout algorithm of the node (and visited its children)");
+            ui.strong("content / inherent")
+                .on_hover_text("sizing modes: content size ignores the size styles of the node, inherent size uses them");
+            ui.strong("min / max content")
+                .on_hover_text("calls with min-content or max-content available space");
+            ui.end_row();
+            row_recursive(ui, tree, root, 0, &stats, *threshold, clicked);
+        });
+}
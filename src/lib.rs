@@ -1,4 +1,23 @@
 #![warn(clippy::all, rust_2018_idioms)]
 
+mod animation;
 mod app;
+mod benchmark;
+mod css;
+mod document;
+mod fuzz;
+mod gallery;
+mod inspector;
+mod layout_trace;
+mod lint;
+mod paint_settings;
+mod responsive;
+mod rounding;
+mod selection;
+mod stress;
+mod style_class;
+mod style_docs;
+mod style_field;
+mod style_variant;
 pub use app::TemplateApp;
+pub use benchmark::run_cli as run_benchmark_cli;
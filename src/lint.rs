@@ -0,0 +1,205 @@
+//! Finds common mistakes in the styles of the tree, like fields that have no effect or contradict each other.
+use std::collections::HashMap;
+
+use taffy::{
+    AlignItems, Dimension, Display, FlexDirection, LengthPercentageAuto, NodeId, Position, Style,
+    TaffyTree,
+};
+
+use crate::style_field::StyleField;
+
+/// A possible mistake in the style of a node.
+#[derive(Debug, Clone)]
+pub struct LintWarning {
+    /// The field that causes the warning
+    pub field: StyleField,
+    pub message: String,
+}
+
+/// The warnings of the nodes, linted at most once per layout pass, since every edit of a style is followed by a layout.
+#[derive(Debug, Default)]
+pub struct LintCache {
+    /// The layout run the warnings were linted after.
+    runs: usize,
+    warnings: HashMap<NodeId, Vec<LintWarning>>,
+}
+impl LintCache {
+    /// Drops the warnings if the tree was laid out again since they were linted.
+    pub fn invalidate(&mut self, runs: usize) {
+        if self.runs != runs {
+            self.runs = runs;
+            self.warnings.clear();
+        }
+    }
+    /// The warnings of the node, linted on the first call after [`LintCache::invalidate`].
+    pub fn lint(&mut self, tree: &TaffyTree, node_id: NodeId) -> &[LintWarning] {
+        self.warnings
+            .entry(node_id)
+            .or_insert_with(|| lint_node(tree, node_id))
+    }
+}
+
+/// Lints the style of a single node, in the context of its parent and ancestors.
+pub fn lint_node(tree: &TaffyTree, node_id: NodeId) -> Vec<LintWarning> {
+    let mut warnings = vec![];
+    let Ok(style) = tree.style(node_id) else {
+        return warnings;
+    };
+    let parent = tree.parent(node_id);
+    let parent_display = parent
+        .and_then(|parent| tree.style(parent).ok())
+        .map(|parent_style| parent_style.display);
+    // hidden nodes are not laid out, so nothing has an effect on them
+    if style.display == Display::None || parent_display == Some(Display::None) {
+        return warnings;
+    }
+    let mut warn = |field: StyleField, message: String| {
+        warnings.push(LintWarning { field, message });
+    };
+
+    // fields that were set, but aren't used by the display mode of the node or its parent
+    for &field in StyleField::ALL {
+        if field.is_equal(style, &Style::DEFAULT) || field.applies_to(style.display, parent_display)
+        {
+            continue;
+        }
+        // item fields would apply with another parent, container fields with another display on the node itself
+        let is_item_field = field.applies_to(style.display, Some(Display::Flex))
+            || field.applies_to(style.display, Some(Display::Grid));
+        let reason = match parent_display {
+            Some(display) if is_item_field => format!("the parent is display: {display:?}"),
+            None if is_item_field => "this is the root node, which has no parent".to_string(),
+            _ => format!("this node is display: {:?}", style.display),
+        };
+        warn(
+            field,
+            format!("{} has no effect, because {reason}", field.name()),
+        );
+    }
+
+    // percentage sizes need a definite size on the parent to resolve against
+    for (axis, size, horizontal) in [
+        ("width", style.size.width, true),
+        ("height", style.size.height, false),
+    ] {
+        if matches!(size, Dimension::Percent(_))
+            && !parent.is_some_and(|parent| has_definite_size(tree, parent, horizontal))
+        {
+            warn(
+                StyleField::Size,
+                format!(
+                    "percentage {axis} may behave like auto: the parent has no definite {axis} to resolve against \
+                    (a fixed {axis}, a percentage of a definite {axis}, or stretched to a definite {axis} by its own parent)"
+                ),
+            );
+        }
+    }
+
+    if style.position == Position::Relative {
+        let inset = style.inset;
+        let is_set = |value: LengthPercentageAuto| value != LengthPercentageAuto::Auto;
+        if is_set(inset.left) && is_set(inset.right) {
+            warn(
+                StyleField::Inset,
+                "inset right is ignored, because left is also set on a Relative node".to_string(),
+            );
+        }
+        if is_set(inset.top) && is_set(inset.bottom) {
+            warn(
+                StyleField::Inset,
+                "inset bottom is ignored, because top is also set on a Relative node".to_string(),
+            );
+        }
+        let zero = LengthPercentageAuto::Length(0.0);
+        let insets = [inset.left, inset.right, inset.top, inset.bottom];
+        if insets.iter().any(|&value| is_set(value))
+            && insets.iter().all(|&value| !is_set(value) || value == zero)
+        {
+            warn(
+                StyleField::Inset,
+                "inset has no visible effect: a Relative node is only offset by its insets, and they're all zero"
+                    .to_string(),
+            );
+        }
+    }
+
+    for (axis, min, max) in [
+        ("width", style.min_size.width, style.max_size.width),
+        ("height", style.min_size.height, style.max_size.height),
+    ] {
+        let contradicts = match (min, max) {
+            (Dimension::Length(min), Dimension::Length(max)) => min > max,
+            (Dimension::Percent(min), Dimension::Percent(max)) => min > max,
+            _ => false,
+        };
+        if contradicts {
+            warn(
+                StyleField::MinSize,
+                format!("min {axis} is greater than max {axis}, so max {axis} is ignored"),
+            );
+        }
+    }
+    warnings
+}
+
+/// Whether the node has a definite size on the axis, which percentages of its children can be resolved against:
+/// a fixed size, a percentage of a parent that has a definite size itself, or an auto size that the parent stretches
+/// to a definite size (see [`is_stretched`]).
+fn has_definite_size(tree: &TaffyTree, node_id: NodeId, horizontal: bool) -> bool {
+    let Ok(style) = tree.style(node_id) else {
+        return false;
+    };
+    let size = if horizontal {
+        style.size.width
+    } else {
+        style.size.height
+    };
+    let parent = tree.parent(node_id);
+    match size {
+        Dimension::Length(_) => true,
+        Dimension::Percent(_) => {
+            parent.is_some_and(|parent| has_definite_size(tree, parent, horizontal))
+        }
+        Dimension::Auto => parent.is_some_and(|parent| {
+            let Ok(parent_style) = tree.style(parent) else {
+                return false;
+            };
+            // the grid area is definite once the tracks are sized, so it doesn't depend on the size of the grid
+            is_stretched(style, parent_style, horizontal)
+                && (parent_style.display == Display::Grid
+                    || has_definite_size(tree, parent, horizontal))
+        }),
+    }
+}
+
+/// Whether the parent stretches an auto sized item to fill it on the axis: the cross axis of a flex container,
+/// both axes of a grid area (unless aligned otherwise), or the width of a block container.
+fn is_stretched(style: &Style, parent_style: &Style, horizontal: bool) -> bool {
+    let (margin_start, margin_end) = if horizontal {
+        (style.margin.left, style.margin.right)
+    } else {
+        (style.margin.top, style.margin.bottom)
+    };
+    if style.position == Position::Absolute {
+        return false;
+    }
+    let auto_margins =
+        margin_start == LengthPercentageAuto::Auto || margin_end == LengthPercentageAuto::Auto;
+    let stretch = |alignment: Option<AlignItems>| {
+        !auto_margins && alignment.map_or(true, |alignment| alignment == AlignItems::Stretch)
+    };
+    match parent_style.display {
+        Display::Flex => {
+            let cross_is_horizontal = matches!(
+                parent_style.flex_direction,
+                FlexDirection::Column | FlexDirection::ColumnReverse
+            );
+            horizontal == cross_is_horizontal
+                && stretch(style.align_self.or(parent_style.align_items))
+        }
+        Display::Grid if horizontal => stretch(style.justify_self.or(parent_style.justify_items)),
+        Display::Grid => stretch(style.align_self.or(parent_style.align_items)),
+        Display::Block => horizontal,
+        Display::None => false,
+    }
+}
@@ -0,0 +1,232 @@
+use egui::{ecolor::Hsva, Color32, ComboBox};
+use taffy::NodeId;
+
+/// Decides how the nodes are colored in Node Visuals (and the box model diagram of the inspector).
+/// These are persisted across restarts.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct PaintSettings {
+    pub color_mode: ColorMode,
+    pub fill_mode: FillMode,
+    /// Use [`Palette::dark`] or [`Palette::light`] based on egui's visuals, instead of the custom palette.
+    pub follow_theme: bool,
+    /// The custom palette used when `follow_theme` is disabled.
+    pub palette: Palette,
+}
+impl Default for PaintSettings {
+    fn default() -> Self {
+        Self {
+            color_mode: ColorMode::BoxArea,
+            fill_mode: FillMode::Opaque,
+            follow_theme: true,
+            palette: Palette::light(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ColorMode {
+    /// Each box model area (margin, border, padding, content) has its own color.
+    BoxArea,
+    /// Each depth level of the tree has its own color. The areas are different shades of that color.
+    Depth,
+    /// Each node has its own color. The areas are different shades of that color.
+    Node,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum FillMode {
+    Opaque,
+    /// Fills are see-through, so that the nested structure is visible.
+    Translucent,
+    /// No fills, only the outlines of the areas.
+    Wireframe,
+}
+
+/// Colors of the box model areas.
+#[derive(Debug, Clone, Copy, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Palette {
+    pub margin: Color32,
+    pub border: Color32,
+    pub padding: Color32,
+    pub content: Color32,
+}
+impl Palette {
+    pub fn light() -> Self {
+        Self {
+            margin: Color32::from_hex("#ff7046").unwrap_or_default(),
+            border: Color32::from_hex("#00a6c3").unwrap_or_default(),
+            padding: Color32::from_hex("#fac357").unwrap_or_default(),
+            content: Color32::from_hex("#00c4a8").unwrap_or_default(),
+        }
+    }
+    pub fn dark() -> Self {
+        Self {
+            margin: Color32::from_hex("#a4462b").unwrap_or_default(),
+            border: Color32::from_hex("#006a7d").unwrap_or_default(),
+            padding: Color32::from_hex("#a07c36").unwrap_or_default(),
+            content: Color32::from_hex("#007d6b").unwrap_or_default(),
+        }
+    }
+}
+
+/// The colors of margin, border, padding and content areas of a node
+#[derive(Debug, Clone, Copy)]
+pub struct AreaColors {
+    pub margin: Color32,
+    pub border: Color32,
+    pub padding: Color32,
+    pub content: Color32,
+}
+
+impl PaintSettings {
+    /// The palette that is in effect right now.
+    pub fn palette(&self, dark_mode: bool) -> Palette {
+        if !self.follow_theme {
+            self.palette
+        } else if dark_mode {
+            Palette::dark()
+        } else {
+            Palette::light()
+        }
+    }
+    /// The colors of the areas of a node at `depth` (root is at zero). Fill mode is already applied.
+    pub fn area_colors(&self, palette: &Palette, node_id: NodeId, depth: usize) -> AreaColors {
+        /// golden ratio conjugate, to spread the hues as far away from each other as possible
+        const HUE_STEP: f32 = 0.618_034;
+        let colors = match self.color_mode {
+            ColorMode::BoxArea => AreaColors {
+                margin: palette.margin,
+                border: palette.border,
+                padding: palette.padding,
+                content: palette.content,
+            },
+            ColorMode::Depth | ColorMode::Node => {
+                let seed = if self.color_mode == ColorMode::Depth {
+                    depth as f32
+                } else {
+                    // the lower 32 bits of the id are the slot index, upper bits are the version
+                    (u64::from(node_id) & u32::MAX as u64) as f32
+                };
+                // we keep the brightness of the palette, so that it works with both light and dark themes
+                let value = Hsva::from(palette.content).v;
+                let hue = (seed * HUE_STEP).fract();
+                let shade = |saturation: f32, value_factor: f32| {
+                    Color32::from(Hsva::new(hue, saturation, value * value_factor, 1.0))
+                };
+                AreaColors {
+                    margin: shade(0.3, 1.0),
+                    border: shade(0.8, 0.7),
+                    padding: shade(0.5, 0.9),
+                    content: shade(0.6, 1.0),
+                }
+            }
+        };
+        match self.fill_mode {
+            FillMode::Opaque | FillMode::Wireframe => colors,
+            FillMode::Translucent => AreaColors {
+                margin: colors.margin.gamma_multiply(0.3),
+                border: colors.border.gamma_multiply(0.3),
+                padding: colors.padding.gamma_multiply(0.3),
+                content: colors.content.gamma_multiply(0.3),
+            },
+        }
+    }
+}
+
+pub fn paint_settings_ui(ui: &mut egui::Ui, settings: &mut PaintSettings) {
+    egui::Grid::new("paint settings")
+        .num_columns(2)
+        .striped(true)
+        .show(ui, |ui| {
+            {
+                ui.label("color mode");
+                let mut selected = match settings.color_mode {
+                    ColorMode::BoxArea => 0,
+                    ColorMode::Depth => 1,
+                    ColorMode::Node => 2,
+                };
+                ComboBox::from_id_salt("color mode").show_index(
+                    ui,
+                    &mut selected,
+                    3,
+                    |i| match i {
+                        0 => "per box model area",
+                        1 => "per depth level",
+                        2 => "per node",
+                        _ => unreachable!(),
+                    },
+                );
+                settings.color_mode = match selected {
+                    0 => ColorMode::BoxArea,
+                    1 => ColorMode::Depth,
+                    2 => ColorMode::Node,
+                    _ => unreachable!(),
+                };
+                ui.end_row();
+            }
+            {
+                ui.label("fill mode");
+                let mut selected = match settings.fill_mode {
+                    FillMode::Opaque => 0,
+                    FillMode::Translucent => 1,
+                    FillMode::Wireframe => 2,
+                };
+                ComboBox::from_id_salt("fill mode").show_index(ui, &mut selected, 3, |i| match i {
+                    0 => "Opaque",
+                    1 => "Translucent",
+                    2 => "Wireframe",
+                    _ => unreachable!(),
+                });
+                settings.fill_mode = match selected {
+                    0 => FillMode::Opaque,
+                    1 => FillMode::Translucent,
+                    2 => FillMode::Wireframe,
+                    _ => unreachable!(),
+                };
+                ui.end_row();
+            }
+            {
+                ui.label("palette");
+                ui.vertical(|ui| {
+                    ui.checkbox(&mut settings.follow_theme, "follow egui theme")
+                        .on_hover_text(
+                            "use the light or dark preset based on the current egui theme",
+                        );
+                    ui.add_enabled_ui(!settings.follow_theme, |ui| {
+                        ui.horizontal(|ui| {
+                            if ui.button("light preset").clicked() {
+                                settings.palette = Palette::light();
+                            }
+                            if ui.button("dark preset").clicked() {
+                                settings.palette = Palette::dark();
+                            }
+                        });
+                        egui::Grid::new("palette colors")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                for (name, color) in [
+                                    ("margin", &mut settings.palette.margin),
+                                    ("border", &mut settings.palette.border),
+                                    ("padding", &mut settings.palette.padding),
+                                    ("content", &mut settings.palette.content),
+                                ] {
+                                    ui.label(name);
+                                    ui.color_edit_button_srgba(color);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                });
+                ui.end_row();
+            }
+            {
+                ui.label("theme");
+                egui::widgets::global_theme_preference_buttons(ui);
+                ui.end_row();
+            }
+        });
+    if ui.button("reset paint settings").clicked() {
+        *settings = PaintSettings::default();
+    }
+}
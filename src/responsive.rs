@@ -0,0 +1,414 @@
+//! Lays out the root in a definite available width, to preview how the layout reflows at different widths.
+//! Finds the breakpoints: the widths at which lines wrap differently or nodes jump to another position.
+use std::collections::HashMap;
+
+use egui::{Color32, Stroke};
+use taffy::{
+    AvailableSpace, Display, FlexDirection, NodeId, Point, Position, Size, TaffyTree,
+    TraversePartialTree,
+};
+
+use crate::paint_settings::{PaintSettings, Palette};
+use crate::style_variant::StyleVariants;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResponsiveSettings {
+    /// Lay out the root in `width`, instead of min-content.
+    pub enabled: bool,
+    pub width: f32,
+    pub min_width: f32,
+    pub max_width: f32,
+    /// Sweeps the width back and forth between the min and max width.
+    pub animate: bool,
+    /// In pixels per second.
+    pub speed: f32,
+    /// 1 or -1, the direction of the animated sweep.
+    pub direction: f32,
+    /// Shows the layout at several widths side by side in Node Visuals.
+    pub filmstrip: bool,
+    pub filmstrip_frames: usize,
+    /// The distance between the widths that are sampled when looking for breakpoints.
+    pub breakpoint_step: f32,
+}
+impl Default for ResponsiveSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            width: 800.0,
+            min_width: 200.0,
+            max_width: 2000.0,
+            animate: false,
+            speed: 300.0,
+            direction: 1.0,
+            filmstrip: false,
+            filmstrip_frames: 5,
+            breakpoint_step: 10.0,
+        }
+    }
+}
+impl ResponsiveSettings {
+    /// The available space of the root, or `None` if the preview is disabled.
+    pub fn available_space(&self) -> Option<Size<AvailableSpace>> {
+        self.enabled.then(|| width_space(self.width))
+    }
+    /// Advances the animated sweep by `dt` seconds, bouncing at the min and max width.
+    pub fn animate(&mut self, dt: f32) {
+        self.width += self.direction * self.speed * dt;
+        if self.width >= self.max_width {
+            self.width = self.max_width;
+            self.direction = -1.0;
+        } else if self.width <= self.min_width {
+            self.width = self.min_width;
+            self.direction = 1.0;
+        }
+    }
+    pub fn filmstrip_widths(&self) -> Vec<f32> {
+        let frames = self.filmstrip_frames.max(2);
+        let step = (self.max_width - self.min_width) / (frames - 1) as f32;
+        (0..frames)
+            .map(|i| (self.min_width + step * i as f32).round())
+            .collect()
+    }
+}
+fn width_space(width: f32) -> Size<AvailableSpace> {
+    Size {
+        width: AvailableSpace::Definite(width),
+        height: AvailableSpace::MinContent,
+    }
+}
+
+/// A width at which the layout changes discontinuously.
+#[derive(Debug, Clone)]
+pub struct Breakpoint {
+    /// The smallest width with the new layout.
+    pub width: f32,
+    /// The first node that changed.
+    pub node: NodeId,
+    pub description: String,
+}
+
+/// The number of lines (or rows) that the children of each container are in, and the location of each node.
+struct Snapshot {
+    /// The nodes in tree order.
+    nodes: Vec<NodeId>,
+    lines: HashMap<NodeId, usize>,
+    locations: HashMap<NodeId, Point<f32>>,
+}
+impl Snapshot {
+    fn take(
+        tree: &mut TaffyTree,
+        root: NodeId,
+        style_variants: &StyleVariants,
+        width: f32,
+    ) -> Self {
+        fn take_recursive(tree: &TaffyTree, node: NodeId, snapshot: &mut Snapshot) {
+            let layout = tree.layout(node).unwrap();
+            snapshot.nodes.push(node);
+            snapshot.locations.insert(node, layout.location);
+            let style = tree.style(node).unwrap();
+            if style.display == Display::None {
+                return;
+            }
+            // a child starts a new line when it's not after the previous child along the main axis
+            let (column, reverse) = match (style.display, style.flex_direction) {
+                (Display::Flex, FlexDirection::Column) => (true, false),
+                (Display::Flex, FlexDirection::ColumnReverse) => (true, true),
+                (Display::Flex, FlexDirection::RowReverse) => (false, true),
+                _ => (false, false),
+            };
+            let mut lines = 0;
+            let mut previous: Option<f32> = None;
+            for child in tree.child_ids(node) {
+                let child_style = tree.style(child).unwrap();
+                if child_style.display == Display::None
+                    || child_style.position == Position::Absolute
+                {
+                    continue;
+                }
+                let location = tree.layout(child).unwrap().location;
+                let main = if column { location.y } else { location.x };
+                let new_line = previous.map_or(true, |previous| {
+                    if reverse {
+                        main >= previous
+                    } else {
+                        main <= previous
+                    }
+                });
+                lines += usize::from(new_line);
+                previous = Some(main);
+            }
+            snapshot.lines.insert(node, lines);
+            for child in tree.child_ids(node) {
+                take_recursive(tree, child, snapshot);
+            }
+        }
+        style_variants.set_styles(tree, Some(width));
+        tree.compute_layout(root, width_space(width)).unwrap();
+        let mut snapshot = Snapshot {
+            nodes: vec![],
+            lines: HashMap::new(),
+            locations: HashMap::new(),
+        };
+        take_recursive(tree, root, &mut snapshot);
+        snapshot
+    }
+    /// The first node whose lines changed, or that moved further than the width changed, between the snapshots.
+    fn difference(&self, next: &Snapshot, width_change: f32) -> Option<(NodeId, String)> {
+        self.nodes.iter().find_map(|&node| {
+            let lines = (self.lines.get(&node), next.lines.get(&node));
+            if let (Some(&before), Some(&after)) = lines {
+                if before != after {
+                    return Some((node, format!("{before} → {after} lines")));
+                }
+            }
+            let (before, after) = (self.locations.get(&node)?, next.locations.get(&node)?);
+            let (dx, dy) = (after.x - before.x, after.y - before.y);
+            (dx.abs() + dy.abs() > width_change + 1.0)
+                .then(|| (node, format!("jumps by {dx:.0} × {dy:.0}")))
+        })
+    }
+}
+
+/// Lays out a copy of the tree (with the matching style variants) at widths from `min_width` to `max_width`
+/// and finds the breakpoints, to the pixel.
+pub fn find_breakpoints(
+    tree: &TaffyTree,
+    root: NodeId,
+    settings: &ResponsiveSettings,
+    style_variants: &StyleVariants,
+) -> Vec<Breakpoint> {
+    let mut tree = tree.clone();
+    let step = settings.breakpoint_step.max(1.0);
+    let mut breakpoints = vec![];
+    let mut low = settings.min_width;
+    let mut low_snapshot = Snapshot::take(&mut tree, root, style_variants, low);
+    while low < settings.max_width {
+        let high = (low + step).min(settings.max_width);
+        let high_snapshot = Snapshot::take(&mut tree, root, style_variants, high);
+        if low_snapshot
+            .difference(&high_snapshot, high - low)
+            .is_some()
+        {
+            // bisect down to a pixel
+            let (mut a, mut b) = (low, high);
+            let mut a_snapshot = Snapshot::take(&mut tree, root, style_variants, a);
+            while b - a > 1.0 {
+                let middle = ((a + b) / 2.0).floor();
+                if middle <= a {
+                    break;
+                }
+                let middle_snapshot = Snapshot::take(&mut tree, root, style_variants, middle);
+                if a_snapshot
+                    .difference(&middle_snapshot, middle - a)
+                    .is_some()
+                {
+                    b = middle;
+                } else {
+                    a = middle;
+                    a_snapshot = middle_snapshot;
+                }
+            }
+            let b_snapshot = Snapshot::take(&mut tree, root, style_variants, b);
+            if let Some((node, description)) = a_snapshot.difference(&b_snapshot, b - a) {
+                breakpoints.push(Breakpoint {
+                    width: b,
+                    node,
+                    description,
+                });
+            }
+        }
+        low = high;
+        low_snapshot = high_snapshot;
+    }
+    breakpoints
+}
+
+/// The settings of the preview and the breakpoints.
+/// Clicking a breakpoint sets the width to it, and `clicked` to its node. The nodes are shown with `node_label`.
+pub fn responsive_ui(
+    ui: &mut egui::Ui,
+    settings: &mut ResponsiveSettings,
+    breakpoints: &[Breakpoint],
+    node_label: impl Fn(NodeId) -> String,
+    clicked: &mut Option<NodeId>,
+) -> bool {
+    ui.checkbox(
+        &mut settings.enabled,
+        "lay out the root in a definite width",
+    )
+    .on_hover_text("otherwise the root is laid out in min-content");
+    ui.add_enabled_ui(settings.enabled, |ui| {
+        ui.add(
+            egui::Slider::new(&mut settings.width, settings.min_width..=settings.max_width)
+                .suffix("px")
+                .text("width"),
+        );
+        ui.horizontal(|ui| {
+            ui.label("from");
+            ui.add(egui::DragValue::new(&mut settings.min_width).range(0.0..=settings.max_width));
+            ui.label("to");
+            ui.add(
+                egui::DragValue::new(&mut settings.max_width).range(settings.min_width..=10_000.0),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.toggle_value(&mut settings.animate, "▶ sweep");
+            ui.add(
+                egui::DragValue::new(&mut settings.speed)
+                    .range(10.0..=5000.0)
+                    .suffix(" px/s"),
+            );
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut settings.filmstrip, "filmstrip")
+                .on_hover_text("shows the layout at several widths side by side in Node Visuals");
+            ui.add(
+                egui::DragValue::new(&mut settings.filmstrip_frames)
+                    .range(2..=12)
+                    .suffix(" frames"),
+            );
+        });
+    });
+    ui.separator();
+    let find = ui
+        .horizontal(|ui| {
+            let find = ui
+                .button("find breakpoints")
+                .on_hover_text(
+                    "lays out a copy of the tree at every step from the min to the max width",
+                )
+                .clicked();
+            ui.label("step");
+            ui.add(
+                egui::DragValue::new(&mut settings.breakpoint_step)
+                    .range(1.0..=500.0)
+                    .suffix("px"),
+            );
+            find
+        })
+        .inner;
+    for breakpoint in breakpoints {
+        let text = format!(
+            "{}px: {} {}",
+            breakpoint.width,
+            node_label(breakpoint.node),
+            breakpoint.description
+        );
+        if ui.selectable_label(false, text).clicked() {
+            settings.enabled = true;
+            settings.animate = false;
+            settings.width = breakpoint.width;
+            *clicked = Some(breakpoint.node);
+        }
+    }
+    find
+}
+
+/// The layouts of the tree at the widths of the filmstrip.
+#[derive(Debug)]
+pub struct Filmstrip {
+    /// The edit count of the tree, and the widths that the frames were laid out in.
+    key: (usize, Vec<f32>),
+    frames: Vec<Frame>,
+}
+#[derive(Debug)]
+struct Frame {
+    width: f32,
+    height: f32,
+    /// The nodes in tree order, with their depth and rect relative to the root.
+    nodes: Vec<(NodeId, usize, egui::Rect)>,
+}
+impl Filmstrip {
+    /// Lays out a copy of the tree at each width. `tree_edits` is the number of edits of the tree so far.
+    pub fn new(
+        tree: &TaffyTree,
+        root: NodeId,
+        settings: &ResponsiveSettings,
+        style_variants: &StyleVariants,
+        tree_edits: usize,
+    ) -> Self {
+        fn nodes_recursive(
+            tree: &TaffyTree,
+            node: NodeId,
+            offset: egui::Vec2,
+            depth: usize,
+            nodes: &mut Vec<(NodeId, usize, egui::Rect)>,
+        ) {
+            let layout = tree.layout(node).unwrap();
+            let min = egui::pos2(layout.location.x, layout.location.y) + offset;
+            let size = egui::vec2(layout.size.width, layout.size.height);
+            nodes.push((node, depth, egui::Rect::from_min_size(min, size)));
+            for child in tree.child_ids(node) {
+                nodes_recursive(tree, child, min.to_vec2(), depth + 1, nodes);
+            }
+        }
+        let widths = settings.filmstrip_widths();
+        let mut tree = tree.clone();
+        let frames = widths
+            .iter()
+            .map(|&width| {
+                style_variants.set_styles(&mut tree, Some(width));
+                tree.compute_layout(root, width_space(width)).unwrap();
+                let mut nodes = vec![];
+                nodes_recursive(&tree, root, egui::Vec2::ZERO, 0, &mut nodes);
+                Frame {
+                    width,
+                    height: tree.layout(root).unwrap().size.height,
+                    nodes,
+                }
+            })
+            .collect();
+        Self {
+            key: (tree_edits, widths),
+            frames,
+        }
+    }
+    /// Whether the tree was edited or the widths changed since the frames were laid out.
+    pub fn is_stale(&self, settings: &ResponsiveSettings, tree_edits: usize) -> bool {
+        self.key.0 != tree_edits || self.key.1 != settings.filmstrip_widths()
+    }
+}
+
+/// The layout at several widths side by side, scaled down to fit. Clicking a frame sets the width of the preview to it.
+pub fn filmstrip_ui(
+    ui: &mut egui::Ui,
+    filmstrip: &Filmstrip,
+    settings: &mut ResponsiveSettings,
+    paint_settings: &PaintSettings,
+    palette: &Palette,
+) {
+    /// The width of the frame of the max width.
+    const MAX_FRAME_WIDTH: f32 = 250.0;
+    let scale = MAX_FRAME_WIDTH / settings.max_width.max(1.0);
+    ui.horizontal_top(|ui| {
+        for frame in &filmstrip.frames {
+            ui.vertical(|ui| {
+                let selected = settings.enabled && (settings.width - frame.width).abs() < 0.5;
+                if ui
+                    .selectable_label(selected, format!("{}px", frame.width))
+                    .clicked()
+                {
+                    settings.enabled = true;
+                    settings.width = frame.width;
+                }
+                let (rect, _) = ui.allocate_exact_size(
+                    egui::vec2(frame.width, frame.height) * scale,
+                    egui::Sense::hover(),
+                );
+                // the available width
+                ui.painter()
+                    .rect_stroke(rect, 0.0, Stroke::new(1.0_f32, Color32::GRAY));
+                let painter = ui.painter().with_clip_rect(rect.expand(1.0));
+                for &(node, depth, node_rect) in &frame.nodes {
+                    let node_rect = egui::Rect::from_min_size(
+                        rect.min + node_rect.min.to_vec2() * scale,
+                        node_rect.size() * scale,
+                    );
+                    let colors = paint_settings.area_colors(palette, node, depth);
+                    painter.rect_filled(node_rect, 0.0, colors.content);
+                    painter.rect_stroke(node_rect, 0.0, Stroke::new(1.0_f32, colors.border));
+                }
+            });
+        }
+    });
+}
@@ -0,0 +1,104 @@
+//! Compares the layouts before and after taffy rounds them to whole pixels, to find sub-pixel seams.
+//! Taffy rounds the locations relative to the parent, but the sizes on the absolute pixel grid,
+//! so the rounded edges of a node can end up more than half a pixel from the unrounded ones.
+use std::collections::HashMap;
+
+use taffy::{Layout, NodeId, Point, Size, TaffyTree, TraversePartialTree};
+
+/// Nodes whose rounded edges are further than this from the unrounded ones are highlighted.
+const SUBPIXEL_THRESHOLD: f32 = 0.5;
+
+/// The layouts of the nodes before rounding.
+/// Taffy keeps them when rounding is enabled, but `layout` only returns them while rounding is disabled.
+/// Only valid if the tree was laid out with rounding enabled.
+pub fn unrounded_layouts(
+    tree: &mut TaffyTree,
+    nodes: impl IntoIterator<Item = NodeId>,
+) -> HashMap<NodeId, Layout> {
+    tree.disable_rounding();
+    let layouts = nodes
+        .into_iter()
+        .map(|node| (node, *tree.layout(node).unwrap()))
+        .collect();
+    tree.enable_rounding();
+    layouts
+}
+
+/// How far rounding moved the edges of a node.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundingError {
+    /// The unrounded absolute location minus the rounded one.
+    pub drift: Point<f32>,
+    pub unrounded_size: Size<f32>,
+    /// The largest distance between a rounded edge and its unrounded edge.
+    pub distance: f32,
+}
+
+/// The nodes whose edges rounding moved by more than half a pixel.
+/// `unrounded` must have the layouts of every node below `root`.
+pub fn subpixel_nodes(
+    tree: &TaffyTree,
+    root: NodeId,
+    unrounded: &HashMap<NodeId, Layout>,
+) -> HashMap<NodeId, RoundingError> {
+    fn subpixel_recursive(
+        tree: &TaffyTree,
+        node: NodeId,
+        unrounded: &HashMap<NodeId, Layout>,
+        (rounded_parent, unrounded_parent): (Point<f32>, Point<f32>),
+        errors: &mut HashMap<NodeId, RoundingError>,
+    ) {
+        let Some(unrounded_layout) = unrounded.get(&node) else {
+            return;
+        };
+        let rounded_layout = tree.layout(node).unwrap();
+        let rounded_location = Point {
+            x: rounded_parent.x + rounded_layout.location.x,
+            y: rounded_parent.y + rounded_layout.location.y,
+        };
+        let unrounded_location = Point {
+            x: unrounded_parent.x + unrounded_layout.location.x,
+            y: unrounded_parent.y + unrounded_layout.location.y,
+        };
+        let drift = Point {
+            x: unrounded_location.x - rounded_location.x,
+            y: unrounded_location.y - rounded_location.y,
+        };
+        let distance = [
+            drift.x,
+            drift.y,
+            drift.x + unrounded_layout.size.width - rounded_layout.size.width,
+            drift.y + unrounded_layout.size.height - rounded_layout.size.height,
+        ]
+        .into_iter()
+        .fold(0.0_f32, |max, distance| max.max(distance.abs()));
+        if distance > SUBPIXEL_THRESHOLD {
+            errors.insert(
+                node,
+                RoundingError {
+                    drift,
+                    unrounded_size: unrounded_layout.size,
+                    distance,
+                },
+            );
+        }
+        for child in tree.child_ids(node) {
+            subpixel_recursive(
+                tree,
+                child,
+                unrounded,
+                (rounded_location, unrounded_location),
+                errors,
+            );
+        }
+    }
+    let mut errors = HashMap::new();
+    subpixel_recursive(
+        tree,
+        root,
+        unrounded,
+        (Point::ZERO, Point::ZERO),
+        &mut errors,
+    );
+    errors
+}
@@ -0,0 +1,83 @@
+use taffy::{NodeId, TaffyTree, TraversePartialTree};
+
+/// How a click on a node changes the selection, based on the held modifiers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectMode {
+    /// Select only the clicked node.
+    Replace,
+    /// ctrl/cmd + click: add the clicked node to the selection, or remove it if it's already selected.
+    Toggle,
+    /// shift + click: select all the nodes from the focused node up to the clicked node, in tree order.
+    Range,
+}
+impl SelectMode {
+    pub fn from_modifiers(modifiers: egui::Modifiers) -> Self {
+        if modifiers.shift {
+            SelectMode::Range
+        } else if modifiers.command {
+            SelectMode::Toggle
+        } else {
+            SelectMode::Replace
+        }
+    }
+}
+
+/// Applies a click on `node` to the selection.
+/// `focused` is the node used by the inspector and the single node actions (add child, delete etc..),
+/// and `others` are the rest of the selected nodes.
+pub fn select(
+    tree: &TaffyTree,
+    root: NodeId,
+    focused: &mut NodeId,
+    others: &mut Vec<NodeId>,
+    node: NodeId,
+    mode: SelectMode,
+) {
+    match mode {
+        SelectMode::Replace => {
+            *focused = node;
+            others.clear();
+        }
+        SelectMode::Toggle => {
+            if node == *focused {
+                if let Some(next) = others.pop() {
+                    *focused = next;
+                }
+            } else if let Some(index) = others.iter().position(|&n| n == node) {
+                others.remove(index);
+            } else {
+                others.push(*focused);
+                *focused = node;
+            }
+        }
+        SelectMode::Range => {
+            let order = tree_order(tree, root);
+            let (Some(a), Some(b)) = (
+                order.iter().position(|&n| n == *focused),
+                order.iter().position(|&n| n == node),
+            ) else {
+                return;
+            };
+            // the focused node is the anchor of the range, so it stays focused
+            others.clear();
+            others.extend(
+                order[a.min(b)..=a.max(b)]
+                    .iter()
+                    .filter(|&&n| n != *focused),
+            );
+        }
+    }
+}
+
+/// All the nodes of the tree in the order they're shown in the tree panel (depth first, pre-order).
+fn tree_order(tree: &TaffyTree, root: NodeId) -> Vec<NodeId> {
+    fn order_recursive(tree: &TaffyTree, node: NodeId, order: &mut Vec<NodeId>) {
+        order.push(node);
+        for child in tree.child_ids(node) {
+            order_recursive(tree, child, order);
+        }
+    }
+    let mut order = vec![];
+    order_recursive(tree, root, &mut order);
+    order
+}
@@ -0,0 +1,278 @@
+//! Generates large random trees, to see how taffy (and the editor) behave with thousands of nodes.
+use taffy::{AlignItems, Display, FlexDirection, FlexWrap, JustifyContent, Size, Style};
+
+use crate::document::{Document, DocumentNode};
+use crate::gallery::{fraction, pct, px};
+
+/// The settings of the stress tree generator. The same settings always generate the same tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StressSettings {
+    pub seed: u64,
+    /// The number of levels of containers below the root.
+    pub depth: usize,
+    /// The maximum number of children of a container. Each container gets 1 to `fan_out` children.
+    pub fan_out: usize,
+    /// The relative weights of flex, grid and block containers.
+    pub flex_weight: u32,
+    pub grid_weight: u32,
+    pub block_weight: u32,
+    /// Randomizes padding, gap, direction, alignment, sizes etc.. instead of using the same style for every container.
+    pub randomize_styles: bool,
+    /// Leaves are paragraphs: wrapping rows of word boxes, which behave like text (min-content is the longest word).
+    pub text_leaves: bool,
+}
+impl Default for StressSettings {
+    fn default() -> Self {
+        Self {
+            seed: 1,
+            depth: 4,
+            fan_out: 6,
+            flex_weight: 2,
+            grid_weight: 1,
+            block_weight: 1,
+            randomize_styles: true,
+            text_leaves: true,
+        }
+    }
+}
+
+/// The generator stops adding nodes at this many, so that large settings can't hang the app or run out of memory.
+pub const MAX_NODE_COUNT: usize = 200_000;
+/// The maximum number of words of a text leaf.
+const MAX_WORDS: usize = 12;
+/// The size of a character in a word box.
+const CHAR_WIDTH: f32 = 7.0;
+const LINE_HEIGHT: f32 = 16.0;
+
+impl StressSettings {
+    /// The node count if every container got `fan_out` children (and every text leaf the maximum number of words).
+    pub fn max_node_count(&self) -> usize {
+        let mut level = 1_usize;
+        let mut total = 1_usize;
+        for _ in 0..self.depth {
+            level = level.saturating_mul(self.fan_out);
+            total = total.saturating_add(level);
+        }
+        if self.text_leaves {
+            total = total.saturating_add(level.saturating_mul(MAX_WORDS));
+        }
+        total
+    }
+    /// Generates the tree, stopping at [`MAX_NODE_COUNT`] nodes.
+    pub fn generate(&self) -> Document {
+        let mut rng = Rng(self.seed);
+        let mut budget = MAX_NODE_COUNT;
+        let mut root = self.container(&mut rng, 0, &mut budget);
+        // a fixed width, so that percentages and wrapping have something to resolve against
+        root.style.size.width = px(1200.0);
+        Document {
+            default_style: Style::DEFAULT,
+            classes: Default::default(),
+            root,
+        }
+    }
+    /// `budget` is the number of nodes that can still be added.
+    fn container(&self, rng: &mut Rng, depth: usize, budget: &mut usize) -> DocumentNode {
+        if depth == self.depth {
+            return self.leaf(rng, budget);
+        }
+        *budget = budget.saturating_sub(1);
+        let total_weight = self.flex_weight + self.grid_weight + self.block_weight;
+        let roll = rng.below(total_weight.max(1) as usize) as u32;
+        let display = if total_weight == 0 || roll < self.flex_weight {
+            Display::Flex
+        } else if roll < self.flex_weight + self.grid_weight {
+            Display::Grid
+        } else {
+            Display::Block
+        };
+        let mut style = Style {
+            display,
+            padding: px(4.0),
+            border: px(1.0),
+            ..Style::DEFAULT
+        };
+        // gap only applies to flex and grid containers
+        let has_gap = display != Display::Block;
+        if has_gap {
+            style.gap = px(4.0);
+        }
+        let child_count = 1 + rng.below(self.fan_out.max(1));
+        if display == Display::Grid {
+            let columns = if self.randomize_styles {
+                1 + rng.below(4)
+            } else {
+                3
+            };
+            style.grid_template_columns = (0..columns).map(|_| fraction(1.0)).collect();
+        }
+        if self.randomize_styles {
+            style.padding = px(rng.below(9) as f32);
+            if has_gap {
+                style.gap = px(rng.below(9) as f32);
+            }
+            if rng.chance(0.3) {
+                style.margin = px(rng.below(5) as f32);
+            }
+            if display == Display::Flex {
+                style.flex_direction = *rng.pick(&[
+                    FlexDirection::Row,
+                    FlexDirection::Column,
+                    FlexDirection::RowReverse,
+                    FlexDirection::ColumnReverse,
+                ]);
+                if rng.chance(0.5) {
+                    style.flex_wrap = FlexWrap::Wrap;
+                }
+                style.align_items = Some(*rng.pick(&[
+                    AlignItems::Start,
+                    AlignItems::Center,
+                    AlignItems::Stretch,
+                    AlignItems::Baseline,
+                ]));
+                style.justify_content = Some(*rng.pick(&[
+                    JustifyContent::Start,
+                    JustifyContent::Center,
+                    JustifyContent::SpaceBetween,
+                    JustifyContent::SpaceAround,
+                ]));
+            }
+        }
+        let mut children = vec![];
+        for _ in 0..child_count {
+            if *budget == 0 {
+                break;
+            }
+            let mut child = self.container(rng, depth + 1, budget);
+            if self.randomize_styles {
+                if display == Display::Flex && rng.chance(0.5) {
+                    child.style.flex_grow = rng.below(3) as f32;
+                }
+                if rng.chance(0.2) {
+                    child.style.size.width = pct(0.2 + 0.8 * rng.unit());
+                }
+            }
+            children.push(child);
+        }
+        DocumentNode::with_children(style, children)
+    }
+    fn leaf(&self, rng: &mut Rng, budget: &mut usize) -> DocumentNode {
+        *budget = budget.saturating_sub(1);
+        if !self.text_leaves {
+            let size = |rng: &mut Rng| px(20.0 + rng.below(100) as f32);
+            return DocumentNode::leaf(Style {
+                size: Size {
+                    width: size(rng),
+                    height: size(rng),
+                },
+                ..Style::DEFAULT
+            });
+        }
+        // a paragraph: words wrap onto the next line when they don't fit, like text
+        let word_count = (1 + rng.below(MAX_WORDS)).min(*budget);
+        *budget -= word_count;
+        let words = (0..word_count)
+            .map(|_| {
+                let chars = 2 + rng.below(9);
+                DocumentNode::leaf(Style {
+                    size: Size {
+                        width: px(chars as f32 * CHAR_WIDTH),
+                        height: px(LINE_HEIGHT),
+                    },
+                    flex_shrink: 0.0,
+                    ..Style::DEFAULT
+                })
+            })
+            .collect();
+        DocumentNode::with_children(
+            Style {
+                flex_wrap: FlexWrap::Wrap,
+                gap: Size {
+                    width: px(CHAR_WIDTH),
+                    height: px(0.0),
+                },
+                ..Style::DEFAULT
+            },
+            words,
+        )
+    }
+}
+
+/// A tiny seeded random number generator (splitmix64), so that a seed always generates the same tree.
+/// Also used by the fuzzer, so that a seed always makes the same mutations.
+pub(crate) struct Rng(pub(crate) u64);
+impl Rng {
+    pub(crate) fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+    /// A number in `0..n`.
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        (self.next() % n.max(1) as u64) as usize
+    }
+    /// A number in `0.0..1.0`.
+    pub(crate) fn unit(&mut self) -> f32 {
+        (self.next() >> 40) as f32 / (1_u64 << 24) as f32
+    }
+    pub(crate) fn chance(&mut self, probability: f32) -> bool {
+        self.unit() < probability
+    }
+    pub(crate) fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+}
+
+/// The settings of the generator. Returns true if "generate" was clicked.
+pub fn stress_settings_ui(ui: &mut egui::Ui, settings: &mut StressSettings) -> bool {
+    egui::Grid::new("stress settings")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("seed");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.seed));
+                if ui.button("🎲").on_hover_text("random seed").clicked() {
+                    settings.seed = Rng(settings.seed ^ ui.input(|i| i.time).to_bits()).next();
+                }
+            });
+            ui.end_row();
+            ui.label("depth");
+            ui.add(egui::Slider::new(&mut settings.depth, 0..=10));
+            ui.end_row();
+            ui.label("fan-out");
+            ui.add(egui::Slider::new(&mut settings.fan_out, 1..=20))
+                .on_hover_text("each container gets 1 to fan-out children");
+            ui.end_row();
+            ui.label("display mix");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut settings.flex_weight).range(0..=10).prefix("flex "));
+                ui.add(egui::DragValue::new(&mut settings.grid_weight).range(0..=10).prefix("grid "));
+                ui.add(egui::DragValue::new(&mut settings.block_weight).range(0..=10).prefix("block "));
+            })
+            .response
+            .on_hover_text("the relative weights of each display mode for the containers");
+            ui.end_row();
+            ui.label("styles");
+            ui.checkbox(&mut settings.randomize_styles, "randomize")
+                .on_hover_text("random padding, gap, margin, direction, wrap, alignment, flex_grow and percentage widths");
+            ui.end_row();
+            ui.label("leaves");
+            ui.checkbox(&mut settings.text_leaves, "text")
+                .on_hover_text("paragraphs of word boxes that wrap like text, instead of fixed size boxes");
+            ui.end_row();
+        });
+    let max_node_count = settings.max_node_count();
+    ui.label(format!("up to {max_node_count} nodes"));
+    let within_budget = max_node_count <= MAX_NODE_COUNT;
+    if !within_budget {
+        ui.colored_label(
+            ui.visuals().error_fg_color,
+            format!("⚠ more than {MAX_NODE_COUNT} nodes. lower the depth or fan-out"),
+        );
+    }
+    ui.add_enabled(within_budget, egui::Button::new("generate"))
+        .on_hover_text("replaces the current document with the generated tree")
+        .clicked()
+}
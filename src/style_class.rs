@@ -0,0 +1,116 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use taffy::{NodeId, Style, TaffyTree};
+
+use crate::style_field::StyleField;
+
+/// Named styles (like css classes) that can be shared by many nodes.
+/// A node that uses a class gets the style of the class, except for the fields that it overrides locally.
+#[derive(Debug, Default)]
+pub struct StyleClasses {
+    pub classes: BTreeMap<String, Style>,
+    /// The class used by each node (if any)
+    pub members: HashMap<NodeId, ClassMembership>,
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ClassMembership {
+    pub class: String,
+    /// The fields that were edited on the node itself, and thus don't follow the class anymore.
+    pub overrides: BTreeSet<StyleField>,
+}
+
+impl StyleClasses {
+    /// Makes the node use `class`, discarding any previous overrides.
+    pub fn apply(&mut self, tree: &mut TaffyTree, node_id: NodeId, class: &str) {
+        let Some(class_style) = self.classes.get(class) else {
+            return;
+        };
+        tree.set_style(node_id, class_style.clone()).unwrap();
+        self.members.insert(
+            node_id,
+            ClassMembership {
+                class: class.to_string(),
+                overrides: BTreeSet::new(),
+            },
+        );
+    }
+    /// The node stops using its class, but keeps its current style.
+    pub fn detach(&mut self, node_id: NodeId) {
+        self.members.remove(&node_id);
+    }
+    /// Updates the styles of all nodes using `class` after the class style has been edited.
+    pub fn propagate(&self, tree: &mut TaffyTree, class: &str) {
+        let Some(class_style) = self.classes.get(class) else {
+            return;
+        };
+        for (&node_id, membership) in &self.members {
+            if membership.class != class {
+                continue;
+            }
+            let Ok(node_style) = tree.style(node_id) else {
+                continue;
+            };
+            let mut style = class_style.clone();
+            for field in &membership.overrides {
+                field.copy(node_style, &mut style);
+            }
+            tree.set_style(node_id, style).unwrap();
+        }
+    }
+    /// Marks the fields that differ between `old` and `new` as overridden, if the node uses a class.
+    pub fn record_edit(&mut self, node_id: NodeId, old: &Style, new: &Style) {
+        let Some(membership) = self.members.get_mut(&node_id) else {
+            return;
+        };
+        for &field in StyleField::ALL {
+            if !field.is_equal(old, new) {
+                membership.overrides.insert(field);
+            }
+        }
+    }
+    /// `None` if the node doesn't use a class. Otherwise, whether the field is overridden by the node.
+    pub fn is_overridden(&self, node_id: NodeId, field: StyleField) -> Option<bool> {
+        self.members
+            .get(&node_id)
+            .map(|membership| membership.overrides.contains(&field))
+    }
+    /// Removes the override of the field and copies its value from the class into `style`.
+    pub fn inherit_field(&mut self, node_id: NodeId, field: StyleField, style: &mut Style) {
+        let Some(membership) = self.members.get_mut(&node_id) else {
+            return;
+        };
+        membership.overrides.remove(&field);
+        if let Some(class_style) = self.classes.get(&membership.class) {
+            field.copy(class_style, style);
+        }
+    }
+    pub fn member_count(&self, class: &str) -> usize {
+        self.members
+            .values()
+            .filter(|membership| membership.class == class)
+            .count()
+    }
+    /// Returns false if the new name is already taken.
+    pub fn rename(&mut self, old: &str, new: &str) -> bool {
+        if self.classes.contains_key(new) {
+            return false;
+        }
+        let Some(style) = self.classes.remove(old) else {
+            return false;
+        };
+        self.classes.insert(new.to_string(), style);
+        for membership in self.members.values_mut() {
+            if membership.class == old {
+                membership.class = new.to_string();
+            }
+        }
+        true
+    }
+    /// Removes the class. The nodes using it keep their current styles.
+    pub fn remove(&mut self, class: &str) {
+        self.classes.remove(class);
+        self.members
+            .retain(|_, membership| membership.class != class);
+    }
+}
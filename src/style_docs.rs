@@ -0,0 +1,380 @@
+//! Short documentation of every [`StyleField`], shown as tooltips in the style editors.
+//! It's compiled into the binary, so that it works offline.
+use crate::style_field::StyleField;
+
+pub struct FieldDoc {
+    /// The name of the css property
+    pub css_name: &'static str,
+    pub summary: &'static str,
+    /// The possible values and what they do
+    pub values: &'static [(&'static str, &'static str)],
+    /// The layout modes that use this field
+    pub applies_to: &'static str,
+    /// The section of the css spec that defines the property
+    pub spec: &'static str,
+}
+
+const LENGTH: (&str, &str) = ("Length", "a fixed size in pixels");
+const PERCENT: (&str, &str) = (
+    "Percent",
+    "a fraction of the parent's (containing block's) size, where 1.0 is 100%",
+);
+const AUTO: (&str, &str) = ("Auto", "decided by the layout algorithm");
+const SIZE_VALUES: &[(&str, &str)] = &[LENGTH, PERCENT, AUTO];
+const RECT_VALUES: &[(&str, &str)] = &[LENGTH, PERCENT];
+const TRACK_VALUES: &[(&str, &str)] = &[
+    ("100px / 50%", "a fixed track size"),
+    ("1fr", "a fraction of the remaining space"),
+    (
+        "auto",
+        "sized by the content, and stretched to fill the leftover space",
+    ),
+    (
+        "min-content / max-content",
+        "the smallest / largest size of the content",
+    ),
+    (
+        "fit-content(100px)",
+        "max-content, but at most the given size",
+    ),
+    ("minmax(min, max)", "a size between min and max"),
+    (
+        "repeat(n | auto-fill | auto-fit, tracks)",
+        "repeats the tracks n times, or as many times as they fit (templates only)",
+    ),
+];
+const PLACEMENT_VALUES: &[(&str, &str)] = &[
+    ("auto", "placed by the auto-placement algorithm"),
+    (
+        "n",
+        "starts (or ends) at line n. negative lines count from the end",
+    ),
+    ("span n", "spans n tracks"),
+    ("start / end", "both the start and end lines"),
+];
+const ALIGN_ITEMS_VALUES: &[(&str, &str)] = &[
+    ("Start / End", "pack to the start / end of the axis"),
+    (
+        "FlexStart / FlexEnd",
+        "like Start / End, but respects the reversed flex directions",
+    ),
+    ("Center", "center in the available space"),
+    ("Baseline", "align the baselines of the items"),
+    (
+        "Stretch",
+        "stretch to fill the cross axis (if the size is auto)",
+    ),
+];
+const ALIGN_CONTENT_VALUES: &[(&str, &str)] = &[
+    ("Start / End", "pack the lines/tracks to the start / end"),
+    (
+        "FlexStart / FlexEnd",
+        "like Start / End, but respects the reversed flex directions",
+    ),
+    ("Center", "pack the lines/tracks in the center"),
+    ("Stretch", "stretch the lines/tracks to fill the space"),
+    (
+        "SpaceBetween",
+        "distribute the space between the lines/tracks, none at the edges",
+    ),
+    (
+        "SpaceEvenly",
+        "equal space between and around the lines/tracks",
+    ),
+    (
+        "SpaceAround",
+        "equal space around each line/track, so the edges get half of it",
+    ),
+];
+
+pub fn field_doc(field: StyleField) -> FieldDoc {
+    match field {
+        StyleField::Display => FieldDoc {
+            css_name: "display",
+            summary: "The layout algorithm used for the children of this node.",
+            values: &[
+                ("Block", "children are stacked vertically, like block level css boxes"),
+                ("Flex", "children are laid out with flexbox"),
+                ("Grid", "children are placed in a css grid"),
+                ("None", "the node and its children are hidden and take no space"),
+            ],
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-display-3/#the-display-properties",
+        },
+        StyleField::BoxSizing => FieldDoc {
+            css_name: "box-sizing",
+            summary: "Whether the size fields include the padding and border.",
+            values: &[
+                ("BorderBox", "size includes padding and border"),
+                ("ContentBox", "size is of the content only, padding and border are added on top"),
+            ],
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-sizing-3/#box-sizing",
+        },
+        StyleField::Overflow => FieldDoc {
+            css_name: "overflow",
+            summary: "What happens to the content that doesn't fit in the node, per axis.",
+            values: &[
+                ("Visible", "content overflows, and contributes to the parent's min-content size"),
+                ("Clip", "content is clipped, and the automatic min size becomes zero"),
+                ("Hidden", "like Clip, but the node is a scroll container without scrollbars"),
+                ("Scroll", "like Hidden, and space is reserved for a scrollbar (scrollbar_width)"),
+            ],
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-overflow-3/#overflow-properties",
+        },
+        StyleField::ScrollbarWidth => FieldDoc {
+            css_name: "scrollbar-width",
+            summary: "The space reserved for scrollbars, on the axes with `Overflow::Scroll`.",
+            values: &[("number", "the width of the scrollbar in pixels")],
+            applies_to: "nodes with Overflow::Scroll",
+            spec: "https://www.w3.org/TR/css-scrollbars-1/#scrollbar-width",
+        },
+        StyleField::Position => FieldDoc {
+            css_name: "position",
+            summary: "Whether the node takes part in the layout of its siblings.",
+            values: &[
+                ("Relative", "laid out normally, and then offset by inset"),
+                ("Absolute", "taken out of the flow and positioned by inset relative to the parent"),
+            ],
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-position-3/#position-property",
+        },
+        StyleField::Inset => FieldDoc {
+            css_name: "inset (top, right, bottom, left)",
+            summary: "The offsets of an Absolute node from the edges of its parent, or the visual offset of a Relative node.",
+            values: SIZE_VALUES,
+            applies_to: "all nodes, mostly Position::Absolute",
+            spec: "https://www.w3.org/TR/css-position-3/#insets",
+        },
+        StyleField::Size => FieldDoc {
+            css_name: "width / height",
+            summary: "The preferred size of the node.",
+            values: SIZE_VALUES,
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-sizing-3/#preferred-size-properties",
+        },
+        StyleField::MinSize => FieldDoc {
+            css_name: "min-width / min-height",
+            summary: "The node is never smaller than this. It wins over max_size.",
+            values: SIZE_VALUES,
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-sizing-3/#min-size-properties",
+        },
+        StyleField::MaxSize => FieldDoc {
+            css_name: "max-width / max-height",
+            summary: "The node is never larger than this.",
+            values: SIZE_VALUES,
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-sizing-3/#max-size-properties",
+        },
+        StyleField::AspectRatio => FieldDoc {
+            css_name: "aspect-ratio",
+            summary: "Width / height. If one of the sizes is auto, it's computed from the other.",
+            values: &[
+                ("disabled", "no preferred aspect ratio"),
+                ("number", "width divided by height, e.g. 1.777 for 16:9"),
+            ],
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-sizing-4/#aspect-ratio",
+        },
+        StyleField::Margin => FieldDoc {
+            css_name: "margin",
+            summary: "The space outside the border of the node.",
+            values: SIZE_VALUES,
+            applies_to: "all nodes. auto margins absorb the free space in flex and grid",
+            spec: "https://www.w3.org/TR/css-box-4/#margins",
+        },
+        StyleField::Padding => FieldDoc {
+            css_name: "padding",
+            summary: "The space between the border and the content of the node.",
+            values: RECT_VALUES,
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-box-4/#paddings",
+        },
+        StyleField::Border => FieldDoc {
+            css_name: "border-width",
+            summary: "The width of the border of the node.",
+            values: RECT_VALUES,
+            applies_to: "all nodes",
+            spec: "https://www.w3.org/TR/css-backgrounds-3/#border-width",
+        },
+        StyleField::Gap => FieldDoc {
+            css_name: "gap (column-gap / row-gap)",
+            summary: "The space between the items (flex) or tracks (grid). width is between columns, height is between rows.",
+            values: RECT_VALUES,
+            applies_to: "flex and grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#gaps",
+        },
+        StyleField::AlignItems => FieldDoc {
+            css_name: "align-items",
+            summary: "The default align_self of the children: how they're aligned in the cross axis (flex) or in their grid area's column axis (grid).",
+            values: ALIGN_ITEMS_VALUES,
+            applies_to: "flex and grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#align-items-property",
+        },
+        StyleField::AlignSelf => FieldDoc {
+            css_name: "align-self",
+            summary: "Overrides the parent's align_items for this node.",
+            values: ALIGN_ITEMS_VALUES,
+            applies_to: "children of flex and grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#align-self-property",
+        },
+        StyleField::JustifyItems => FieldDoc {
+            css_name: "justify-items",
+            summary: "The default justify_self of the children: how they're aligned in their grid area's row axis.",
+            values: ALIGN_ITEMS_VALUES,
+            applies_to: "grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#justify-items-property",
+        },
+        StyleField::JustifySelf => FieldDoc {
+            css_name: "justify-self",
+            summary: "Overrides the parent's justify_items for this node.",
+            values: ALIGN_ITEMS_VALUES,
+            applies_to: "children of grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#justify-self-property",
+        },
+        StyleField::AlignContent => FieldDoc {
+            css_name: "align-content",
+            summary: "How the free space is distributed between the lines (wrapping flex) or rows (grid).",
+            values: ALIGN_CONTENT_VALUES,
+            applies_to: "flex (with wrap) and grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#align-justify-content",
+        },
+        StyleField::JustifyContent => FieldDoc {
+            css_name: "justify-content",
+            summary: "How the free space is distributed between the items in the main axis (flex) or between the columns (grid).",
+            values: ALIGN_CONTENT_VALUES,
+            applies_to: "flex and grid containers",
+            spec: "https://www.w3.org/TR/css-align-3/#align-justify-content",
+        },
+        StyleField::FlexDirection => FieldDoc {
+            css_name: "flex-direction",
+            summary: "The main axis, along which the children are placed.",
+            values: &[
+                ("Row", "left to right"),
+                ("Column", "top to bottom"),
+                ("RowReverse", "right to left"),
+                ("ColumnReverse", "bottom to top"),
+            ],
+            applies_to: "flex containers",
+            spec: "https://www.w3.org/TR/css-flexbox-1/#flex-direction-property",
+        },
+        StyleField::FlexWrap => FieldDoc {
+            css_name: "flex-wrap",
+            summary: "Whether the children wrap into multiple lines when they don't fit.",
+            values: &[
+                ("NoWrap", "a single line, the items shrink or overflow"),
+                ("Wrap", "items wrap into new lines"),
+                ("WrapReverse", "like Wrap, but the lines are stacked in reverse"),
+            ],
+            applies_to: "flex containers",
+            spec: "https://www.w3.org/TR/css-flexbox-1/#flex-wrap-property",
+        },
+        StyleField::FlexBasis => FieldDoc {
+            css_name: "flex-basis",
+            summary: "The initial main size of the item, before the free space is distributed with flex_grow / flex_shrink.",
+            values: &[LENGTH, PERCENT, ("Auto", "use the size (or the content size)")],
+            applies_to: "children of flex containers",
+            spec: "https://www.w3.org/TR/css-flexbox-1/#flex-basis-property",
+        },
+        StyleField::FlexGrow => FieldDoc {
+            css_name: "flex-grow",
+            summary: "How much of the positive free space the item takes, relative to its siblings.",
+            values: &[("number", "0 means the item doesn't grow")],
+            applies_to: "children of flex containers",
+            spec: "https://www.w3.org/TR/css-flexbox-1/#flex-grow-property",
+        },
+        StyleField::FlexShrink => FieldDoc {
+            css_name: "flex-shrink",
+            summary: "How much the item shrinks when there isn't enough space, relative to its siblings (weighted by flex_basis).",
+            values: &[("number", "0 means the item doesn't shrink")],
+            applies_to: "children of flex containers",
+            spec: "https://www.w3.org/TR/css-flexbox-1/#flex-shrink-property",
+        },
+        StyleField::GridTemplateRows => FieldDoc {
+            css_name: "grid-template-rows",
+            summary: "The sizes of the explicit rows of the grid.",
+            values: TRACK_VALUES,
+            applies_to: "grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#track-sizing",
+        },
+        StyleField::GridTemplateColumns => FieldDoc {
+            css_name: "grid-template-columns",
+            summary: "The sizes of the explicit columns of the grid.",
+            values: TRACK_VALUES,
+            applies_to: "grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#track-sizing",
+        },
+        StyleField::GridAutoRows => FieldDoc {
+            css_name: "grid-auto-rows",
+            summary: "The sizes of the implicit rows, which are created for items placed outside of the explicit grid.",
+            values: TRACK_VALUES,
+            applies_to: "grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#auto-tracks",
+        },
+        StyleField::GridAutoColumns => FieldDoc {
+            css_name: "grid-auto-columns",
+            summary: "The sizes of the implicit columns, which are created for items placed outside of the explicit grid.",
+            values: TRACK_VALUES,
+            applies_to: "grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#auto-tracks",
+        },
+        StyleField::GridAutoFlow => FieldDoc {
+            css_name: "grid-auto-flow",
+            summary: "How the auto-placement algorithm places the items without an explicit position.",
+            values: &[
+                ("Row", "fill each row in turn, adding rows as needed"),
+                ("Column", "fill each column in turn, adding columns as needed"),
+                ("RowDense / ColumnDense", "like Row / Column, but fills the holes left by earlier items"),
+            ],
+            applies_to: "grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#grid-auto-flow-property",
+        },
+        StyleField::GridRow => FieldDoc {
+            css_name: "grid-row",
+            summary: "The rows that the item occupies, given as start and end lines.",
+            values: PLACEMENT_VALUES,
+            applies_to: "children of grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#placement-shorthands",
+        },
+        StyleField::GridColumn => FieldDoc {
+            css_name: "grid-column",
+            summary: "The columns that the item occupies, given as start and end lines.",
+            values: PLACEMENT_VALUES,
+            applies_to: "children of grid containers",
+            spec: "https://www.w3.org/TR/css-grid-2/#placement-shorthands",
+        },
+        StyleField::TextAlign => FieldDoc {
+            css_name: "text-align (legacy -webkit values)",
+            summary: "Aligns the block children that are narrower than the container, like the legacy `-webkit-left/right/center` values.",
+            values: &[
+                ("Auto", "no alignment, children use their margins"),
+                ("LegacyLeft / LegacyRight / LegacyCenter", "align the children to the left / right / center"),
+            ],
+            applies_to: "block containers",
+            spec: "https://www.w3.org/TR/css-text-3/#text-align-property",
+        },
+    }
+}
+
+/// A help icon, with the documentation of the field as its tooltip.
+pub fn field_help_ui(ui: &mut egui::Ui, field: StyleField) {
+    ui.weak("❓").on_hover_ui(|ui| {
+        let doc = field_doc(field);
+        ui.set_max_width(400.0);
+        ui.strong(doc.css_name);
+        ui.label(doc.summary);
+        egui::Grid::new("field doc values")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (value, description) in doc.values {
+                    ui.monospace(*value);
+                    ui.label(*description);
+                    ui.end_row();
+                }
+            });
+        ui.label(format!("applies to: {}", doc.applies_to));
+        ui.hyperlink_to("css spec ↗", doc.spec);
+    });
+}
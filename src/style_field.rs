@@ -0,0 +1,225 @@
+use taffy::{Display, NodeId, Rect, Size, Style, TaffyTree};
+
+/// Declares [`StyleField`] along with the display name, the [`Style`] member and the [`StyleSection`] of each variant.
+macro_rules! style_fields {
+    ($($variant:ident => $name:literal, $member:ident, $section:ident;)*) => {
+        /// A field of [`Style`] that can be edited in the style editor.
+        #[derive(
+            Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+        )]
+        pub enum StyleField {
+            $($variant,)*
+        }
+        impl StyleField {
+            /// All the fields, in the order that they are displayed in the style editor.
+            pub const ALL: &'static [StyleField] = &[$(StyleField::$variant,)*];
+            pub fn name(self) -> &'static str {
+                match self {
+                    $(StyleField::$variant => $name,)*
+                }
+            }
+            pub fn section(self) -> StyleSection {
+                match self {
+                    $(StyleField::$variant => StyleSection::$section,)*
+                }
+            }
+            /// Copies the value of this field from `src` to `dst`.
+            pub fn copy(self, src: &Style, dst: &mut Style) {
+                match self {
+                    $(StyleField::$variant => dst.$member = src.$member.clone(),)*
+                }
+            }
+            /// Whether this field has the same value in both styles.
+            pub fn is_equal(self, a: &Style, b: &Style) -> bool {
+                match self {
+                    $(StyleField::$variant => a.$member == b.$member,)*
+                }
+            }
+        }
+    };
+}
+
+style_fields! {
+    Display => "display", display, Box;
+    BoxSizing => "box sizing", box_sizing, Box;
+    Overflow => "overflow", overflow, Box;
+    ScrollbarWidth => "scrollbar width", scrollbar_width, Box;
+    Position => "position", position, Position;
+    Inset => "inset", inset, Position;
+    Size => "size", size, Size;
+    MinSize => "min_size", min_size, Size;
+    MaxSize => "max_size", max_size, Size;
+    AspectRatio => "aspect_ratio", aspect_ratio, Size;
+    Margin => "margin", margin, Spacing;
+    Padding => "padding", padding, Spacing;
+    Border => "border", border, Spacing;
+    Gap => "gap", gap, Spacing;
+    AlignItems => "align_items", align_items, Alignment;
+    AlignSelf => "align_self", align_self, Alignment;
+    JustifyItems => "justify_items", justify_items, Alignment;
+    JustifySelf => "justify_self", justify_self, Alignment;
+    AlignContent => "align_content", align_content, Alignment;
+    JustifyContent => "justify_content", justify_content, Alignment;
+    FlexDirection => "flex_direction", flex_direction, Flex;
+    FlexWrap => "flex_wrap", flex_wrap, Flex;
+    FlexBasis => "flex_basis", flex_basis, Flex;
+    FlexGrow => "flex_grow", flex_grow, Flex;
+    FlexShrink => "flex_shrink", flex_shrink, Flex;
+    GridTemplateRows => "grid_template_rows", grid_template_rows, Grid;
+    GridTemplateColumns => "grid_template_columns", grid_template_columns, Grid;
+    GridAutoRows => "grid_auto_rows", grid_auto_rows, Grid;
+    GridAutoColumns => "grid_auto_columns", grid_auto_columns, Grid;
+    GridAutoFlow => "grid_auto_flow", grid_auto_flow, Grid;
+    GridRow => "grid_row", grid_row, Grid;
+    GridColumn => "grid_column", grid_column, Grid;
+    TextAlign => "text_align", text_align, Block;
+}
+
+/// The collapsible sections of the style editor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StyleSection {
+    Box,
+    Position,
+    Size,
+    Spacing,
+    Alignment,
+    Flex,
+    Grid,
+    Block,
+}
+impl StyleSection {
+    pub const ALL: &'static [StyleSection] = &[
+        StyleSection::Box,
+        StyleSection::Position,
+        StyleSection::Size,
+        StyleSection::Spacing,
+        StyleSection::Alignment,
+        StyleSection::Flex,
+        StyleSection::Grid,
+        StyleSection::Block,
+    ];
+    pub fn name(self) -> &'static str {
+        match self {
+            StyleSection::Box => "Box",
+            StyleSection::Position => "Position",
+            StyleSection::Size => "Size",
+            StyleSection::Spacing => "Spacing",
+            StyleSection::Alignment => "Alignment",
+            StyleSection::Flex => "Flex",
+            StyleSection::Grid => "Grid",
+            StyleSection::Block => "Block",
+        }
+    }
+}
+
+impl StyleField {
+    /// Whether the field has any effect on a node with `display`, whose parent has `parent_display` (`None` for the root).
+    /// Container fields depend on the node's own display mode, and item fields on the parent's.
+    pub fn applies_to(self, display: Display, parent_display: Option<Display>) -> bool {
+        let container = |modes: &[Display]| modes.contains(&display);
+        let item = |modes: &[Display]| parent_display.is_some_and(|parent| modes.contains(&parent));
+        match self {
+            StyleField::Gap
+            | StyleField::AlignItems
+            | StyleField::AlignContent
+            | StyleField::JustifyContent => container(&[Display::Flex, Display::Grid]),
+            StyleField::AlignSelf => item(&[Display::Flex, Display::Grid]),
+            StyleField::JustifyItems
+            | StyleField::GridTemplateRows
+            | StyleField::GridTemplateColumns
+            | StyleField::GridAutoRows
+            | StyleField::GridAutoColumns
+            | StyleField::GridAutoFlow => container(&[Display::Grid]),
+            StyleField::JustifySelf | StyleField::GridRow | StyleField::GridColumn => {
+                item(&[Display::Grid])
+            }
+            StyleField::FlexDirection | StyleField::FlexWrap => container(&[Display::Flex]),
+            StyleField::FlexBasis | StyleField::FlexGrow | StyleField::FlexShrink => {
+                item(&[Display::Flex])
+            }
+            StyleField::TextAlign => container(&[Display::Block]),
+            StyleField::Display
+            | StyleField::BoxSizing
+            | StyleField::Overflow
+            | StyleField::ScrollbarWidth
+            | StyleField::Position
+            | StyleField::Inset
+            | StyleField::Size
+            | StyleField::MinSize
+            | StyleField::MaxSize
+            | StyleField::AspectRatio
+            | StyleField::Margin
+            | StyleField::Padding
+            | StyleField::Border => true,
+        }
+    }
+}
+
+impl StyleField {
+    /// Copies the parts of this field that differ between `old` and `new` to `dst`.
+    /// For a rect or a size only the edited sides or axes are copied, so the other ones of `dst` are kept.
+    pub fn copy_edit(self, old: &Style, new: &Style, dst: &mut Style) {
+        fn part<T: PartialEq + Clone>(old: &T, new: &T, dst: &mut T) {
+            if old != new {
+                *dst = new.clone();
+            }
+        }
+        fn rect<T: PartialEq + Clone>(old: &Rect<T>, new: &Rect<T>, dst: &mut Rect<T>) {
+            part(&old.left, &new.left, &mut dst.left);
+            part(&old.right, &new.right, &mut dst.right);
+            part(&old.top, &new.top, &mut dst.top);
+            part(&old.bottom, &new.bottom, &mut dst.bottom);
+        }
+        fn size<T: PartialEq + Clone>(old: &Size<T>, new: &Size<T>, dst: &mut Size<T>) {
+            part(&old.width, &new.width, &mut dst.width);
+            part(&old.height, &new.height, &mut dst.height);
+        }
+        match self {
+            StyleField::Overflow => {
+                part(&old.overflow.x, &new.overflow.x, &mut dst.overflow.x);
+                part(&old.overflow.y, &new.overflow.y, &mut dst.overflow.y);
+            }
+            StyleField::Inset => rect(&old.inset, &new.inset, &mut dst.inset),
+            StyleField::Margin => rect(&old.margin, &new.margin, &mut dst.margin),
+            StyleField::Padding => rect(&old.padding, &new.padding, &mut dst.padding),
+            StyleField::Border => rect(&old.border, &new.border, &mut dst.border),
+            StyleField::Size => size(&old.size, &new.size, &mut dst.size),
+            StyleField::MinSize => size(&old.min_size, &new.min_size, &mut dst.min_size),
+            StyleField::MaxSize => size(&old.max_size, &new.max_size, &mut dst.max_size),
+            StyleField::Gap => size(&old.gap, &new.gap, &mut dst.gap),
+            StyleField::GridRow => {
+                part(
+                    &old.grid_row.start,
+                    &new.grid_row.start,
+                    &mut dst.grid_row.start,
+                );
+                part(&old.grid_row.end, &new.grid_row.end, &mut dst.grid_row.end);
+            }
+            StyleField::GridColumn => {
+                part(
+                    &old.grid_column.start,
+                    &new.grid_column.start,
+                    &mut dst.grid_column.start,
+                );
+                part(
+                    &old.grid_column.end,
+                    &new.grid_column.end,
+                    &mut dst.grid_column.end,
+                );
+            }
+            _ => {
+                if !self.is_equal(old, new) {
+                    self.copy(new, dst);
+                }
+            }
+        }
+    }
+}
+
+/// Sets the style of the node, unless it already has that style (or isn't in the tree).
+/// Setting a style marks the node and its ancestors dirty, so this keeps them from being laid out again for nothing.
+pub fn set_style_if_changed(tree: &mut TaffyTree, node: NodeId, style: Style) {
+    if tree.style(node).is_ok_and(|current| *current != style) {
+        tree.set_style(node, style).unwrap();
+    }
+}
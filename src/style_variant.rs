@@ -0,0 +1,331 @@
+//! Per-node style variants for ranges of the available width, like css media queries.
+//! Taffy has no media queries, so the matching variants are applied to the styles before `compute_layout`.
+use std::collections::{BTreeSet, HashMap};
+
+use taffy::{NodeId, Style, TaffyTree};
+
+use crate::responsive::ResponsiveSettings;
+use crate::style_field::{set_style_if_changed, StyleField};
+
+/// The fields of a node's style that differ when the available width is in a range.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct StyleVariant {
+    /// Inclusive, like `@media (min-width: ..)`. `None` means no lower bound.
+    pub min_width: Option<f32>,
+    /// Inclusive, like `@media (max-width: ..)`. `None` means no upper bound.
+    pub max_width: Option<f32>,
+    /// The fields that were edited while the variant was active. The other fields follow the base style.
+    pub overrides: BTreeSet<StyleField>,
+    pub style: Style,
+}
+impl StyleVariant {
+    pub fn matches(&self, width: f32) -> bool {
+        self.min_width.map_or(true, |min| width >= min)
+            && self.max_width.map_or(true, |max| width <= max)
+    }
+    pub fn range_text(&self) -> String {
+        match (self.min_width, self.max_width) {
+            (Some(min), Some(max)) => format!("{min}–{max}px"),
+            (Some(min), None) => format!("≥ {min}px"),
+            (None, Some(max)) => format!("≤ {max}px"),
+            (None, None) => "any width".to_string(),
+        }
+    }
+    /// A width in the range, to preview the variant.
+    fn sample_width(&self) -> f32 {
+        match (self.min_width, self.max_width) {
+            (Some(min), _) => min,
+            (None, Some(max)) => max,
+            (None, None) => 800.0,
+        }
+    }
+}
+
+/// The base style and the variants of a node.
+#[derive(Debug, Clone)]
+pub struct NodeVariants {
+    /// The style when no variant matches.
+    pub base: Style,
+    pub variants: Vec<StyleVariant>,
+    /// The variant that the node's style in the tree comes from, and that edits go to. `None` for the base style.
+    pub active: Option<usize>,
+}
+impl NodeVariants {
+    /// The last variant that matches `width` wins, like the last matching css rule.
+    fn matching(&self, width: Option<f32>) -> Option<usize> {
+        let width = width?;
+        self.variants
+            .iter()
+            .rposition(|variant| variant.matches(width))
+    }
+    /// The base style with the overridden fields of the variant.
+    fn style(&self, variant: Option<usize>) -> Style {
+        let mut style = self.base.clone();
+        if let Some(variant) = variant.and_then(|index| self.variants.get(index)) {
+            for field in &variant.overrides {
+                field.copy(&variant.style, &mut style);
+            }
+        }
+        style
+    }
+}
+
+/// The style variants of the nodes that have any.
+#[derive(Debug, Default)]
+pub struct StyleVariants {
+    pub nodes: HashMap<NodeId, NodeVariants>,
+    /// Incremented on every change of the variants, as it changes the layout at other widths.
+    pub version: usize,
+}
+
+impl StyleVariants {
+    /// Saves the changes made to the styles in the tree into the base styles (see [`Self::sync`]),
+    /// then switches each node to the variant that matches `width`. With `None`, the base styles are used.
+    pub fn apply(&mut self, tree: &mut TaffyTree, width: Option<f32>) {
+        let nodes: Vec<NodeId> = self.nodes.keys().copied().collect();
+        for node in nodes {
+            self.sync(tree, node);
+        }
+        for node_variants in self.nodes.values_mut() {
+            node_variants.active = node_variants.matching(width);
+        }
+        self.set_styles(tree, width);
+    }
+    /// Sets the styles of the nodes to the variants that match `width`, without touching the variants.
+    /// Used on copies of the tree, to lay them out at other widths.
+    pub fn set_styles(&self, tree: &mut TaffyTree, width: Option<f32>) {
+        for (&node, node_variants) in &self.nodes {
+            set_style_if_changed(
+                tree,
+                node,
+                node_variants.style(node_variants.matching(width)),
+            );
+        }
+    }
+    /// Saves an edit made in the style editor: the edited fields become overrides of the active variant.
+    /// Without an active variant, the edit goes to the base style.
+    pub fn record_edit(&mut self, node: NodeId, old: &Style, new: &Style) {
+        let Some(node_variants) = self.nodes.get_mut(&node) else {
+            return;
+        };
+        for &field in StyleField::ALL {
+            if field.is_equal(old, new) {
+                continue;
+            }
+            match node_variants
+                .active
+                .and_then(|index| node_variants.variants.get_mut(index))
+            {
+                Some(variant) => {
+                    field.copy(new, &mut variant.style);
+                    variant.overrides.insert(field);
+                }
+                None => field.copy(new, &mut node_variants.base),
+            }
+            self.version += 1;
+        }
+    }
+    /// Copies the fields that were changed in the tree since the last [`Self::apply`] without going through
+    /// [`Self::record_edit`] (class propagation, reset style, loading a keyframe) into the base style.
+    /// The fields that the active variant overrides keep the value of the variant.
+    fn sync(&mut self, tree: &TaffyTree, node: NodeId) {
+        let (Ok(current), Some(node_variants)) = (tree.style(node), self.nodes.get_mut(&node))
+        else {
+            return;
+        };
+        let expected = node_variants.style(node_variants.active);
+        for &field in StyleField::ALL {
+            if !field.is_equal(current, &expected) {
+                field.copy(current, &mut node_variants.base);
+                self.version += 1;
+            }
+        }
+    }
+    /// Adds a variant without overrides. It becomes active on the next [`Self::apply`] if it matches.
+    pub fn add(&mut self, tree: &TaffyTree, node: NodeId, min_width: Option<f32>) {
+        self.sync(tree, node);
+        let Ok(style) = tree.style(node) else {
+            return;
+        };
+        let node_variants = self.nodes.entry(node).or_insert_with(|| NodeVariants {
+            base: style.clone(),
+            variants: vec![],
+            active: None,
+        });
+        node_variants.variants.push(StyleVariant {
+            min_width,
+            max_width: None,
+            overrides: BTreeSet::new(),
+            style: node_variants.base.clone(),
+        });
+        self.version += 1;
+    }
+    /// Removes the variant. Switches the node to the base style if the variant was active.
+    pub fn remove(&mut self, tree: &mut TaffyTree, node: NodeId, index: usize) {
+        self.sync(tree, node);
+        let Some(node_variants) = self.nodes.get_mut(&node) else {
+            return;
+        };
+        node_variants.variants.remove(index);
+        match node_variants.active {
+            Some(active) if active == index => {
+                node_variants.active = None;
+                tree.set_style(node, node_variants.base.clone()).unwrap();
+            }
+            Some(active) if active > index => node_variants.active = Some(active - 1),
+            _ => {}
+        }
+        if node_variants.variants.is_empty() {
+            self.nodes.remove(&node);
+        }
+        self.version += 1;
+    }
+    /// The variant stops overriding the field, and follows the base style again.
+    pub fn inherit_field(
+        &mut self,
+        tree: &mut TaffyTree,
+        node: NodeId,
+        index: usize,
+        field: StyleField,
+    ) {
+        self.sync(tree, node);
+        let Some(node_variants) = self.nodes.get_mut(&node) else {
+            return;
+        };
+        node_variants.variants[index].overrides.remove(&field);
+        if node_variants.active == Some(index) {
+            tree.set_style(node, node_variants.style(Some(index)))
+                .unwrap();
+        }
+        self.version += 1;
+    }
+    /// Forgets the variants of a removed node.
+    pub fn detach(&mut self, node: NodeId) {
+        if self.nodes.remove(&node).is_some() {
+            self.version += 1;
+        }
+    }
+}
+
+/// Which style of the node the style editor edits, if the node has variants.
+pub fn editing_variant_ui(
+    ui: &mut egui::Ui,
+    node: NodeId,
+    style_variants: &StyleVariants,
+    responsive: &ResponsiveSettings,
+) {
+    let Some(node_variants) = style_variants.nodes.get(&node) else {
+        return;
+    };
+    let editing = match node_variants.active {
+        Some(index) => format!("variant {}", node_variants.variants[index].range_text()),
+        None => "base style".to_string(),
+    };
+    ui.horizontal(|ui| {
+        ui.label("editing:");
+        ui.label(egui::RichText::new(editing).strong());
+        if responsive.enabled {
+            ui.weak(format!("(available width {}px)", responsive.width));
+        }
+    });
+    if !responsive.enabled {
+        ui.weak(
+            "the variants apply when the Responsive Preview lays out the root in a definite width",
+        );
+    }
+}
+
+/// The variants of the node. Clicking a variant enables the responsive preview at a width in its range.
+pub fn style_variants_ui(
+    ui: &mut egui::Ui,
+    tree: &mut TaffyTree,
+    node: NodeId,
+    style_variants: &mut StyleVariants,
+    responsive: &mut ResponsiveSettings,
+) {
+    let mut remove = None;
+    let mut inherit = None;
+    let mut bounds_changed = false;
+    if let Some(node_variants) = style_variants.nodes.get_mut(&node) {
+        for (index, variant) in node_variants.variants.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                let label = format!("@ {}", variant.range_text());
+                if ui
+                    .selectable_label(node_variants.active == Some(index), label)
+                    .on_hover_text("click to preview (and edit) this variant")
+                    .clicked()
+                {
+                    responsive.enabled = true;
+                    responsive.animate = false;
+                    responsive.width = variant.sample_width();
+                }
+                bounds_changed |= bound_ui(ui, "min", &mut variant.min_width);
+                bounds_changed |= bound_ui(ui, "max", &mut variant.max_width);
+                if ui
+                    .small_button("🗑")
+                    .on_hover_text("remove the variant")
+                    .clicked()
+                {
+                    remove = Some(index);
+                }
+            });
+            ui.horizontal_wrapped(|ui| {
+                ui.add_space(ui.spacing().indent);
+                if variant.overrides.is_empty() {
+                    ui.weak("no overridden fields");
+                }
+                for &field in &variant.overrides {
+                    if ui
+                        .small_button(format!("{} ✕", field.name()))
+                        .on_hover_text(
+                            "stop overriding this field, and use the value of the base style",
+                        )
+                        .clicked()
+                    {
+                        inherit = Some((index, field));
+                    }
+                }
+            });
+        }
+    }
+    if bounds_changed {
+        style_variants.version += 1;
+    }
+    if let Some((index, field)) = inherit {
+        style_variants.inherit_field(tree, node, index, field);
+    }
+    if let Some(index) = remove {
+        style_variants.remove(tree, node, index);
+    }
+    if ui
+        .button("add variant")
+        .on_hover_text("adds a variant for the widths from the current available width (or 600px) and up. edit the style while it's active to override fields")
+        .clicked()
+    {
+        let min_width = if responsive.enabled {
+            responsive.width
+        } else {
+            600.0
+        };
+        style_variants.add(tree, node, Some(min_width));
+    }
+}
+/// An optional bound of the width range. Returns true if it was changed.
+fn bound_ui(ui: &mut egui::Ui, name: &str, bound: &mut Option<f32>) -> bool {
+    let mut enabled = bound.is_some();
+    let mut changed = false;
+    if ui.checkbox(&mut enabled, name).changed() {
+        *bound = enabled.then_some(600.0);
+        changed = true;
+    }
+    if let Some(value) = bound {
+        changed |= ui
+            .add(
+                egui::DragValue::new(value)
+                    .range(0.0..=10_000.0)
+                    .suffix("px"),
+            )
+            .changed();
+    }
+    changed
+}